@@ -3,7 +3,7 @@ use crate::ecmaths::{affine::ECAffinePoint, ru256::RU256};
 use primitive_types::U256;
 use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct K1;
 
 impl SECP256 for K1 {
@@ -13,20 +13,18 @@ impl SECP256 for K1 {
     // ******************************************************************
 
     fn p() -> RU256 {
-        return RU256::from_str("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F")
-            .unwrap();
+        RU256::from_str("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F").unwrap()
     }
     fn g() -> ECAffinePoint {
-        return ECAffinePoint {
+        ECAffinePoint {
             x: RU256::from_str("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")
                 .unwrap(),
             y: RU256::from_str("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8")
                 .unwrap(),
-        };
+        }
     }
     fn n() -> RU256 {
-        return RU256::from_str("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141")
-            .unwrap();
+        RU256::from_str("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141").unwrap()
     }
 
     fn a() -> RU256 {
@@ -45,8 +43,8 @@ impl SECP256 for K1 {
     fn sqrt_exp_num() -> RU256 {
         let p = Self::p();
         let p_plus_1 = p.v.checked_add(U256::one()).unwrap();
-        return RU256 {
+        RU256 {
             v: p_plus_1.div_mod(U256::from(4)).0,
-        };
+        }
     }
 }
@@ -3,7 +3,7 @@ use crate::ecmaths::{affine::ECAffinePoint, ru256::RU256};
 use primitive_types::U256;
 use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct R1;
 
 impl SECP256 for R1 {
@@ -13,13 +13,11 @@ impl SECP256 for R1 {
     // ******************************************************************
 
     fn p() -> RU256 {
-        return RU256::from_str(
-            "0xFFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
-        )
-        .unwrap();
+        RU256::from_str("0xFFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF")
+            .unwrap()
     }
     fn g() -> ECAffinePoint {
-        return ECAffinePoint {
+        ECAffinePoint {
             x: RU256::from_str(
                 "0x6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
             )
@@ -28,13 +26,11 @@ impl SECP256 for R1 {
                 "0x4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
             )
             .unwrap(),
-        };
+        }
     }
     fn n() -> RU256 {
-        return RU256::from_str(
-            "0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
-        )
-        .unwrap();
+        RU256::from_str("0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551")
+            .unwrap()
     }
 
     fn a() -> RU256 {
@@ -55,8 +51,8 @@ impl SECP256 for R1 {
     fn sqrt_exp_num() -> RU256 {
         let p = Self::p();
         let p_plus_1 = p.v.checked_add(U256::one()).unwrap();
-        return RU256 {
+        RU256 {
             v: p_plus_1.div_mod(U256::from(4)).0,
-        };
+        }
     }
 }
@@ -0,0 +1,456 @@
+use crate::{
+    curves::SECP256,
+    error::Error,
+    signature::{ecdh::SharedSecret, public_key::PublicKey, PrivateKey},
+};
+use aes::Aes128;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use ctr::{
+    cipher::{KeyIvInit, StreamCipher},
+    Ctr128BE,
+};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+// ******************************************************************
+// Elliptic Curve Integrated Encryption Scheme
+// an ephemeral key pair (r, R) per message, z = x(r * K_B)
+//
+// Ethereum (go-ethereum crypto/ecies, devp2p RLPx):
+//   Ke || Km' = ConcatKDF(z, s1), Km = sha256(Km')
+//   c = AES-128-CTR(Ke, iv, m), d = HMAC-SHA256(Km, iv || c || s2)
+//   output: R (65 bytes, uncompressed) || iv || c || d
+//
+// SEC1:
+//   key || nonce = X9.63 KDF(z, s1), the GCM tag takes the place of the HMAC
+//   c || tag = AES-256-GCM(key, nonce, m, aad = s2)
+//   output: R (33 bytes, compressed) || c || tag
+//
+// Reference: https://www.secg.org/sec1-v2.pdf (3.6.1, 5.1)
+//            https://github.com/ethereum/devp2p/blob/master/rlpx.md#ecies-encryption
+// ******************************************************************
+
+const IV_LEN: usize = 16;
+const HMAC_LEN: usize = 32;
+const GCM_NONCE_LEN: usize = 12;
+const GCM_TAG_LEN: usize = 16;
+
+pub fn encrypt_ethereum<C: SECP256 + Clone>(
+    pub_key: &PublicKey<C>,
+    msg: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let mut iv = [0u8; IV_LEN];
+    getrandom::getrandom(&mut iv).map_err(|_| Error::RandomSource)?;
+
+    let ephemeral = PrivateKey::random(pub_key.curve())?;
+    encrypt_ethereum_with(&ephemeral, &iv, pub_key, msg, s1, s2)
+}
+
+pub fn decrypt_ethereum<C: SECP256 + Clone>(
+    priv_key: &PrivateKey,
+    ciphertext: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+    curve: &C,
+) -> Result<Vec<u8>, Error> {
+    if ciphertext.len() < 65 + IV_LEN + HMAC_LEN {
+        return Err(Error::InvalidEncoding);
+    }
+    let (ephemeral, rest) = ciphertext.split_at(65);
+    let (em, tag) = rest.split_at(rest.len() - HMAC_LEN);
+
+    let shared_secret = priv_key.diffie_hellman(&PublicKey::from_sec1(ephemeral, curve)?)?;
+    let (ke, km) = ethereum_keys(&shared_secret, s1);
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(km.as_ref()).unwrap();
+    mac.update(em);
+    mac.update(s2);
+    mac.verify_slice(tag).map_err(|_| Error::Decryption)?;
+
+    let (iv, c) = em.split_at(IV_LEN);
+    let mut msg = c.to_vec();
+    Ctr128BE::<Aes128>::new(ke.as_ref().into(), iv.into()).apply_keystream(&mut msg);
+    Ok(msg)
+}
+
+pub fn encrypt_sec1<C: SECP256 + Clone>(
+    pub_key: &PublicKey<C>,
+    msg: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let ephemeral = PrivateKey::random(pub_key.curve())?;
+    encrypt_sec1_with(&ephemeral, pub_key, msg, s1, s2)
+}
+
+pub fn decrypt_sec1<C: SECP256 + Clone>(
+    priv_key: &PrivateKey,
+    ciphertext: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+    curve: &C,
+) -> Result<Vec<u8>, Error> {
+    if ciphertext.len() < 33 + GCM_TAG_LEN {
+        return Err(Error::InvalidEncoding);
+    }
+    let (ephemeral, c) = ciphertext.split_at(33);
+
+    let shared_secret = priv_key.diffie_hellman(&PublicKey::from_sec1(ephemeral, curve)?)?;
+    let (key, nonce) = sec1_keys(&shared_secret, s1);
+
+    Aes256Gcm::new(key.as_ref().into())
+        .decrypt(
+            Nonce::from_slice(nonce.as_ref()),
+            Payload { msg: c, aad: s2 },
+        )
+        .map_err(|_| Error::Decryption)
+}
+
+fn encrypt_ethereum_with<C: SECP256 + Clone>(
+    ephemeral: &PrivateKey,
+    iv: &[u8; IV_LEN],
+    pub_key: &PublicKey<C>,
+    msg: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let shared_secret = ephemeral.diffie_hellman(pub_key)?;
+    let (ke, km) = ethereum_keys(&shared_secret, s1);
+
+    let mut em = [iv.as_slice(), msg].concat();
+    Ctr128BE::<Aes128>::new(ke.as_ref().into(), iv.into()).apply_keystream(&mut em[IV_LEN..]);
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(km.as_ref()).unwrap();
+    mac.update(&em);
+    mac.update(s2);
+    let tag = mac.finalize().into_bytes();
+
+    Ok([
+        ephemeral.public_key(pub_key.curve())?.to_sec1(false),
+        em,
+        tag.to_vec(),
+    ]
+    .concat())
+}
+
+fn encrypt_sec1_with<C: SECP256 + Clone>(
+    ephemeral: &PrivateKey,
+    pub_key: &PublicKey<C>,
+    msg: &[u8],
+    s1: &[u8],
+    s2: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let shared_secret = ephemeral.diffie_hellman(pub_key)?;
+    let (key, nonce) = sec1_keys(&shared_secret, s1);
+
+    let c = Aes256Gcm::new(key.as_ref().into())
+        .encrypt(Nonce::from_slice(nonce.as_ref()), Payload { msg, aad: s2 })
+        .map_err(|_| Error::Encryption)?;
+
+    Ok([ephemeral.public_key(pub_key.curve())?.to_sec1(true), c].concat())
+}
+
+// Ke (AES-128 key) and Km (HMAC key)
+fn ethereum_keys(
+    shared_secret: &SharedSecret,
+    s1: &[u8],
+) -> (Zeroizing<[u8; 16]>, Zeroizing<[u8; 32]>) {
+    let k = concat_kdf(shared_secret.as_bytes(), s1, 32);
+
+    let mut ke = Zeroizing::new([0u8; 16]);
+    ke.copy_from_slice(&k[..16]);
+    (ke, Zeroizing::new(Sha256::digest(&k[16..]).into()))
+}
+
+// AES-256 key and GCM nonce
+fn sec1_keys(
+    shared_secret: &SharedSecret,
+    s1: &[u8],
+) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; GCM_NONCE_LEN]>) {
+    let k = x963_kdf(shared_secret.as_bytes(), s1, 32 + GCM_NONCE_LEN);
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    let mut nonce = Zeroizing::new([0u8; GCM_NONCE_LEN]);
+    key.copy_from_slice(&k[..32]);
+    nonce.copy_from_slice(&k[32..]);
+    (key, nonce)
+}
+
+// NIST SP 800-56A concatenation KDF: sha256(counter || z || s1) for counter = 1, 2, ...
+fn concat_kdf(z: &[u8], s1: &[u8], len: usize) -> Zeroizing<Vec<u8>> {
+    kdf(len, |counter, hasher| {
+        hasher.update(counter);
+        hasher.update(z);
+        hasher.update(s1);
+    })
+}
+
+// ANSI X9.63 KDF: sha256(z || counter || s1) for counter = 1, 2, ...
+fn x963_kdf(z: &[u8], s1: &[u8], len: usize) -> Zeroizing<Vec<u8>> {
+    kdf(len, |counter, hasher| {
+        hasher.update(z);
+        hasher.update(counter);
+        hasher.update(s1);
+    })
+}
+
+fn kdf(len: usize, input: impl Fn(&[u8; 4], &mut Sha256)) -> Zeroizing<Vec<u8>> {
+    let mut k = Zeroizing::new(Vec::with_capacity(len.next_multiple_of(32)));
+    for counter in 1..=len.div_ceil(32) as u32 {
+        let mut hasher = Sha256::new();
+        input(&counter.to_be_bytes(), &mut hasher);
+        k.extend_from_slice(&hasher.finalize());
+    }
+
+    k.truncate(len);
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        concat_kdf, decrypt_ethereum, decrypt_sec1, encrypt_ethereum, encrypt_ethereum_with,
+        encrypt_sec1, encrypt_sec1_with, x963_kdf,
+    };
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::PrivateKey,
+    };
+
+    fn key(hex: &str) -> PrivateKey {
+        PrivateKey::new(RU256::from_bytes(&hex::decode(hex).unwrap()))
+    }
+
+    // receiver, ephemeral and other (unrelated) keys: the EIP-8 handshake keys
+    // for secp256k1 and the NIST CAVS ECDH dIUT keys for P-256
+    const K1_KEYS: [&str; 3] = [
+        "b71c71a67e1177ad4e901695e1b4b9ee17ae16c6668d313eac2f96dbcda3f291",
+        "869d6ecf5211f1cc60418a13b9d870b22959d0c16f02bec714c960dd2298a32d",
+        "49a7b37aa6f6645917e7b807e9d1c00d4fa71f18343b0d4122a4d2df64dd6fee",
+    ];
+    const R1_KEYS: [&str; 3] = [
+        "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+        "38f65d6dce47676044d58ce5139582d568f64bb16098d179dbab07741dd5caf5",
+        "1accfaf1b97712b85a6f54b148985a1bdc4c9bec0bd258cad4b3d603f49f32c8",
+    ];
+
+    // go-ethereum crypto/ecies/ecies_test.go
+    #[test]
+    fn test_geth_vectors() {
+        // TestKDF
+        let expected = "858b192fa2ed4395e2bf88dd8d5770d67dc284ee539f12da8bceaa45d06ebae0700f1ab918a5f0413b8140f9940d6955f3467fd6672cce1024c5b1effccc0f61";
+        for len in [6, 32, 48, 64] {
+            assert_eq!(
+                hex::encode(concat_kdf(b"input", b"", len).as_slice()),
+                expected[..len * 2]
+            );
+        }
+
+        // TestSharedKeyStatic
+        let prv1 = key("7ebbc6a8358bc76dd73ebc557056702c8cfc34e5cfcd90eb83af0347575fd2ad");
+        let prv2 = key("6a3d6396903245bba5837752b9e0348874e72db0c4e11e9c485a81b4ea4353b9");
+        let shared_secret = prv1.diffie_hellman(&prv2.public_key(&K1).unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(shared_secret.as_bytes()),
+            "167ccc13ac5e8a26b131c3446030c60fbfac6aa8e31149d0869f93626a4cdf62"
+        );
+    }
+
+    #[test]
+    fn test_x963_kdf() {
+        // counter after z, unlike the concatenation KDF
+        let k = x963_kdf(b"input", b"info", 44);
+        assert_eq!(k.len(), 44);
+        assert_ne!(k[..32], concat_kdf(b"input", b"info", 32)[..]);
+        assert_eq!(k[..32], x963_kdf(b"input", b"info", 32)[..]);
+    }
+
+    #[test]
+    fn test_ethereum() {
+        // "Hello, world." with s1 = "", s2 = "shared data 2" and iv = 0b..0b
+        fn check<T: SECP256 + Clone>(curve: &T, keys: [&str; 3], expected: &str) {
+            let [receiver, ephemeral, other] = keys.map(key);
+            let pub_key = receiver.public_key(curve).unwrap();
+            let msg = b"Hello, world.";
+            let iv = [0x0b; 16];
+
+            let ciphertext =
+                encrypt_ethereum_with(&ephemeral, &iv, &pub_key, msg, b"", b"shared data 2")
+                    .unwrap();
+            assert_eq!(hex::encode(&ciphertext), expected);
+            assert_eq!(
+                decrypt_ethereum(&receiver, &ciphertext, b"", b"shared data 2", curve).unwrap(),
+                msg
+            );
+            let fresh = encrypt_ethereum(&pub_key, msg, b"", b"shared data 2").unwrap();
+            assert_eq!(fresh.len(), 65 + 16 + msg.len() + 32);
+            assert_eq!(
+                decrypt_ethereum(&receiver, &fresh, b"", b"shared data 2", curve).unwrap(),
+                msg
+            );
+            // an empty message still carries a tag
+            let empty = encrypt_ethereum_with(&ephemeral, &iv, &pub_key, b"", b"", b"").unwrap();
+            assert_eq!(empty.len(), 65 + 16 + 32);
+            assert_eq!(
+                decrypt_ethereum(&receiver, &empty, b"", b"", curve).unwrap(),
+                b""
+            );
+
+            assert_eq!(
+                decrypt_ethereum(&receiver, &ciphertext, b"", b"shared data", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_ethereum(&receiver, &ciphertext, b"s1", b"shared data 2", curve),
+                Err(Error::Decryption)
+            );
+            let mut tampered = ciphertext.clone();
+            tampered[65 + 16] ^= 1;
+            assert_eq!(
+                decrypt_ethereum(&receiver, &tampered, b"", b"shared data 2", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_ethereum(&other, &ciphertext, b"", b"shared data 2", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_ethereum(&receiver, &ciphertext[..100], b"", b"", curve),
+                Err(Error::InvalidEncoding)
+            );
+        }
+        check(
+            &K1,
+            K1_KEYS,
+            "04654d1044b69c577a44e5f01a1209523adb4026e70c62d1c13a067acabc09d2667a49821a0ad4b634554d330a15a58fe61f8a8e0544b310c6de7b0c8da7528a8d0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0e35c0a5f1ee9659c82b2e772acc5d3903dffe43d88b7637bfdc878909a6da4c49bf1bf6f4b0086621492215a2",
+        );
+        check(
+            &R1,
+            R1_KEYS,
+            "04119f2f047902782ab0c9e27a54aff5eb9b964829ca99c06b02ddba95b0a3f6d08f52b726664cac366fc98ac7a012b2682cbd962e5acb544671d41b9445704d1d0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b00c1374ab6e4e4a5c24dce41f43577915f8fe20d59103d4213753931456835b00af7a7741e8a75483ef8f91ef3",
+        );
+    }
+
+    // EIP-8 / go-ethereum p2p/rlpx handshake test vectors, the ECIES auth
+    // and ack messages of a handshake between two known static keys
+    // https://eips.ethereum.org/EIPS/eip-8#rlpx-handshake
+    #[test]
+    fn test_ethereum_handshake_vectors() {
+        let static_a = key("49a7b37aa6f6645917e7b807e9d1c00d4fa71f18343b0d4122a4d2df64dd6fee");
+        let static_b = key("b71c71a67e1177ad4e901695e1b4b9ee17ae16c6668d313eac2f96dbcda3f291");
+        let ephemeral_b = key("e238eb8e04fee6511ab04c6dd3c89ce097b11f25d584863ac2b6d5b35b1847e4");
+        let nonce_a =
+            hex::decode("7e968bba13b6c50e2c4cd7f241cc0d64d1ac25c7f5952df231ac6a2bda8ee5d6")
+                .unwrap();
+        let nonce_b =
+            hex::decode("559aead08264d5795d3909718cdd05abd49572e84fe55590eef31a88a08fdffd")
+                .unwrap();
+        let pub_a = static_a.public_key(&K1).unwrap().to_sec1(false);
+
+        // auth (pre EIP-8): sig || keccak256(ephemeral-pubk) || pubk || nonce || 0x00
+        let auth = hex::decode("048ca79ad18e4b0659fab4853fe5bc58eb83992980f4c9cc147d2aa31532efd29a3d3dc6a3d89eaf913150cfc777ce0ce4af2758bf4810235f6e6ceccfee1acc6b22c005e9e3a49d6448610a58e98744ba3ac0399e82692d67c1f58849050b3024e21a52c9d3b01d871ff5f210817912773e610443a9ef142e91cdba0bd77b5fdf0769b05671fc35f83d83e4d3b0b000c6b2a1b1bba89e0fc51bf4e460df3105c444f14be226458940d6061c296350937ffd5e3acaceeaaefd3c6f74be8e23e0f45163cc7ebd76220f0128410fd05250273156d548a414444ae2f7dea4dfca2d43c057adb701a715bf59f6fb66b2d1d20f2c703f851cbf5ac47396d9ca65b6260bd141ac4d53e2de585a73d1750780db4c9ee4cd4d225173a4592ee77e2bd94d0be3691f3b406f9bba9b591fc63facc016bfa8").unwrap();
+        let msg = decrypt_ethereum(&static_b, &auth, b"", b"", &K1).unwrap();
+        assert_eq!(msg.len(), 194);
+        assert_eq!(msg[97..161], pub_a[1..]);
+        assert_eq!(msg[161..193], nonce_a);
+        assert_eq!(msg[193], 0);
+
+        // ack (pre EIP-8): ephemeral-pubk || nonce || 0x00
+        let ack = hex::decode("049f8abcfa9c0dc65b982e98af921bc0ba6e4243169348a236abe9df5f93aa69d99cadddaa387662b0ff2c08e9006d5a11a278b1b3331e5aaabf0a32f01281b6f4ede0e09a2d5f585b26513cb794d9635a57563921c04a9090b4f14ee42be1a5461049af4ea7a7f49bf4c97a352d39c8d02ee4acc416388c1c66cec761d2bc1c72da6ba143477f049c9d2dde846c252c111b904f630ac98e51609b3b1f58168ddca6505b7196532e5f85b259a20c45e1979491683fee108e9660edbf38f3add489ae73e3dda2c71bd1497113d5c755e942d1").unwrap();
+        let msg = decrypt_ethereum(&static_a, &ack, b"", b"", &K1).unwrap();
+        assert_eq!(
+            msg[..64],
+            ephemeral_b.public_key(&K1).unwrap().to_sec1(false)[1..]
+        );
+        assert_eq!(msg[64..96], nonce_b);
+        assert_eq!(msg[96..], [0]);
+        assert_eq!(
+            decrypt_ethereum(&static_b, &ack, b"", b"", &K1),
+            Err(Error::Decryption)
+        );
+
+        // auth (EIP-8): the 2 byte size prefix is the shared data s2 and the
+        // message is rlp([sig, pubk, nonce, 4]) followed by padding
+        let auth = hex::decode("01b304ab7578555167be8154d5cc456f567d5ba302662433674222360f08d5f1534499d3678b513b0fca474f3a514b18e75683032eb63fccb16c156dc6eb2c0b1593f0d84ac74f6e475f1b8d56116b849634a8c458705bf83a626ea0384d4d7341aae591fae42ce6bd5c850bfe0b999a694a49bbbaf3ef6cda61110601d3b4c02ab6c30437257a6e0117792631a4b47c1d52fc0f8f89caadeb7d02770bf999cc147d2df3b62e1ffb2c9d8c125a3984865356266bca11ce7d3a688663a51d82defaa8aad69da39ab6d5470e81ec5f2a7a47fb865ff7cca21516f9299a07b1bc63ba56c7a1a892112841ca44b6e0034dee70c9adabc15d76a54f443593fafdc3b27af8059703f88928e199cb122362a4b35f62386da7caad09c001edaeb5f8a06d2b26fb6cb93c52a9fca51853b68193916982358fe1e5369e249875bb8d0d0ec36f917bc5e1eafd5896d46bd61ff23f1a863a8a8dcd54c7b109b771c8e61ec9c8908c733c0263440e2aa067241aaa433f0bb053c7b31a838504b148f570c0ad62837129e547678c5190341e4f1693956c3bf7678318e2d5b5340c9e488eefea198576344afbdf66db5f51204a6961a63ce072c8926c").unwrap();
+        let (size, ciphertext) = auth.split_at(2);
+        assert_eq!(
+            usize::from(u16::from_be_bytes([size[0], size[1]])),
+            ciphertext.len()
+        );
+        let msg = decrypt_ethereum(&static_b, ciphertext, b"", size, &K1).unwrap();
+        assert_eq!(msg[..2], [0xf8, 0xa7]);
+        assert_eq!(msg[71..135], pub_a[1..]);
+        assert_eq!(msg[136..168], nonce_a);
+        assert_eq!(msg[168], 4);
+        assert_eq!(
+            decrypt_ethereum(&static_b, ciphertext, b"", b"", &K1),
+            Err(Error::Decryption)
+        );
+    }
+
+    #[test]
+    fn test_sec1() {
+        // "Hello, world." with s1 = "s1" and s2 = "s2"
+        fn check<T: SECP256 + Clone>(curve: &T, keys: [&str; 3], expected: &str) {
+            let [receiver, ephemeral, other] = keys.map(key);
+            let pub_key = receiver.public_key(curve).unwrap();
+            let msg = b"Hello, world.";
+
+            let ciphertext = encrypt_sec1_with(&ephemeral, &pub_key, msg, b"s1", b"s2").unwrap();
+            assert_eq!(hex::encode(&ciphertext), expected);
+            assert_eq!(ciphertext.len(), 33 + msg.len() + 16);
+            assert_eq!(
+                decrypt_sec1(&receiver, &ciphertext, b"s1", b"s2", curve).unwrap(),
+                msg
+            );
+            // a fresh ephemeral key every time
+            let fresh = encrypt_sec1(&pub_key, msg, b"s1", b"s2").unwrap();
+            assert_ne!(fresh, ciphertext);
+            assert_eq!(
+                decrypt_sec1(&receiver, &fresh, b"s1", b"s2", curve).unwrap(),
+                msg
+            );
+
+            assert_eq!(
+                decrypt_sec1(&receiver, &ciphertext, b"", b"s2", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_sec1(&receiver, &ciphertext, b"s1", b"", curve),
+                Err(Error::Decryption)
+            );
+            let mut tampered = ciphertext.clone();
+            tampered[40] ^= 1;
+            assert_eq!(
+                decrypt_sec1(&receiver, &tampered, b"s1", b"s2", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_sec1(&other, &ciphertext, b"s1", b"s2", curve),
+                Err(Error::Decryption)
+            );
+            assert_eq!(
+                decrypt_sec1(&receiver, &ciphertext[..48], b"s1", b"s2", curve),
+                Err(Error::InvalidEncoding)
+            );
+        }
+        check(
+            &K1,
+            K1_KEYS,
+            "03654d1044b69c577a44e5f01a1209523adb4026e70c62d1c13a067acabc09d26661a2a33dbba4d912ccc43de41c099c7121a9a76dd386aed88d48eff4af",
+        );
+        check(
+            &R1,
+            R1_KEYS,
+            "03119f2f047902782ab0c9e27a54aff5eb9b964829ca99c06b02ddba95b0a3f6d0635b2474eaa4ec52c2220da2391ba5fab1437b710eddb02e8aab7b44dd",
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use super::{jacobian::JacobianPoint, ru256::RU256};
-use crate::curves::SECP256;
+use crate::{curves::SECP256, error::Error};
 use primitive_types::U256;
 use std::str::FromStr;
 
@@ -10,17 +10,101 @@ pub struct ECAffinePoint {
 }
 
 impl ECAffinePoint {
-    pub fn from_hex_coordinates(x: &str, y: &str) -> Self {
-        return Self {
-            x: RU256::from_str(x).unwrap(),
-            y: RU256::from_str(y).unwrap(),
-        };
+    pub fn from_hex_coordinates(x: &str, y: &str) -> Result<Self, Error> {
+        Ok(Self {
+            x: RU256::from_str(x)?,
+            y: RU256::from_str(y)?,
+        })
     }
     pub fn to_hex_string(&self) -> String {
-        return format!("04{}{}", self.x.to_string(), self.y.to_string());
+        format!("04{}{}", self.x, self.y)
     }
     pub fn is_zero_point(&self) -> bool {
-        return self.x == RU256::zero() && self.y == RU256::zero();
+        self.x == RU256::zero() && self.y == RU256::zero()
+    }
+
+    // Checks y ** 2 = x ** 3 + ax + b with both coordinates reduced mod p
+    pub fn is_on_curve<T: SECP256>(&self, _: &T) -> bool {
+        let p = &T::p();
+        if &self.x >= p || &self.y >= p {
+            return false;
+        }
+
+        let y_squared = self
+            .x
+            .exp_mod(&RU256::three(), p)
+            .add_mod(&T::a().mul_mod(&self.x, p), p)
+            .add_mod(&T::b(), p);
+        self.y.mul_mod(&self.y, p) == y_squared
+    }
+
+    // A public key or any other point received from outside has to be on the
+    // curve and cannot be the identity
+    pub fn validate<T: SECP256>(&self, curve: &T) -> Result<(), Error> {
+        if self.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+        if !self.is_on_curve(curve) {
+            return Err(Error::PointNotOnCurve);
+        }
+        Ok(())
+    }
+
+    // Returns the point with the given x and y parity if x is on the curve
+    // **NOTE: the (p + 1) / 4 square root requires p % 4 == 3
+    pub fn from_x<T: SECP256>(x: &RU256, y_odd: bool, _: &T) -> Result<Self, Error> {
+        /*
+         * y ** 2 = x ** 3 + ax + b
+         * y = (y ** 2) ** ((p + 1) / 4)
+         */
+        let p = &T::p();
+        if x >= p {
+            return Err(Error::OutOfRange);
+        }
+
+        let y_squared = x
+            .exp_mod(&RU256::three(), p)
+            .add_mod(&T::a().mul_mod(x, p), p)
+            .add_mod(&T::b(), p);
+        let y = y_squared.exp_mod(&T::sqrt_exp_num(), p);
+        if y.mul_mod(&y, p) != y_squared {
+            return Err(Error::PointNotOnCurve);
+        }
+
+        let y = match y.v.bit(0) == y_odd {
+            true => y,
+            false => p.sub_mod(&y, p),
+        };
+
+        Ok(Self { x: x.clone(), y })
+    }
+
+    // SEC1 compressed encoding: 0x02 / 0x03 (parity of y) || x
+    pub fn to_compressed(&self) -> [u8; 33] {
+        let mut bytes = [0; 33];
+        bytes[0] = 0x02 | self.y.v.bit(0) as u8;
+        self.x.to_bytes(&mut bytes[1..]);
+        bytes
+    }
+
+    pub fn from_compressed<T: SECP256>(bytes: &[u8; 33], curve: &T) -> Result<Self, Error> {
+        match bytes[0] {
+            0x02 | 0x03 => Self::from_x(&RU256::from_bytes(&bytes[1..]), bytes[0] == 0x03, curve),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    // -P = (x, p - y)
+    pub fn negate<T: SECP256>(&self, _: &T) -> Self {
+        if self.is_zero_point() {
+            return Self::zero_point();
+        }
+
+        let p = &T::p();
+        Self {
+            x: self.x.clone(),
+            y: p.sub_mod(&self.y, p),
+        }
     }
 
     // ******************************************************************
@@ -30,10 +114,10 @@ impl ECAffinePoint {
     // ******************************************************************
 
     pub fn zero_point() -> Self {
-        return Self {
+        Self {
             x: RU256::zero(),
             y: RU256::zero(),
-        };
+        }
     }
 
     pub fn add<T: SECP256>(&self, other: &Self, _: &T) -> Self {
@@ -135,7 +219,7 @@ impl ECAffinePoint {
             r = r.double(curve);
             let bit = (scalar.v >> i) & U256::one();
             if bit == U256::one() {
-                r = r.add(&self, curve);
+                r = r.add(self, curve);
             }
 
             i -= 1;
@@ -16,15 +16,15 @@ pub struct JacobianPoint {
  */
 impl JacobianPoint {
     pub fn is_zero_point(&self) -> bool {
-        return self.x == RU256::zero() && self.y == RU256::zero();
+        self.x == RU256::zero() && self.y == RU256::zero()
     }
 
     pub fn zero_point() -> Self {
-        return Self {
+        Self {
             x: RU256::zero(),
             y: RU256::zero(),
             z: RU256::one(),
-        };
+        }
     }
 
     pub fn add<T: SECP256>(&self, other: &Self, curve: &T) -> Self {
@@ -77,7 +77,7 @@ impl JacobianPoint {
         let v = &u1.mul_mod(h2, p);
 
         let x = r
-            .mul_mod(&r, p)
+            .mul_mod(r, p)
             .sub_mod(h3, p)
             .sub_mod(&v.mul_mod(&RU256::two(), p), p);
         let y = r
@@ -145,7 +145,7 @@ impl JacobianPoint {
             r = r.double(curve);
             let bit = (scalar.v >> i) & U256::one();
             if bit == U256::one() {
-                r = r.add(&self, curve);
+                r = r.add(self, curve);
             }
 
             i -= 1;
@@ -184,15 +184,15 @@ impl JacobianPoint {
         let mut i = 255;
 
         let one = U256::one();
-        let self_other = self.add(&other, curve);
+        let self_other = self.add(other, curve);
 
         while i != -1 {
             r = r.double(curve);
 
             match (((a.v >> i) & one == one), ((b.v >> i) & one == one)) {
                 (true, true) => r = r.add(&self_other, curve),
-                (true, false) => r = r.add(&self, curve),
-                (false, true) => r = r.add(&other, curve),
+                (true, false) => r = r.add(self, curve),
+                (false, true) => r = r.add(other, curve),
                 (false, false) => {}
             }
 
@@ -201,20 +201,92 @@ impl JacobianPoint {
 
         r
     }
+
+    // Multiples [0P, 1P, ..., 15P] used as the lookup table of a 4 bit window
+    pub fn window_table<T: SECP256>(&self, curve: &T) -> Vec<Self> {
+        let mut table = vec![Self::zero_point(), self.clone()];
+        for i in 2..16 {
+            let next = table[i - 1].add(self, curve);
+            table.push(next);
+        }
+
+        table
+    }
+
+    // aP + bQ like strauss_shamir_multiplication, but consuming 4 bits of a and b
+    // per step from the precomputed window tables of P and Q
+    pub fn windowed_strauss_shamir_multiplication<T: SECP256>(
+        table_p: &[Self],
+        table_q: &[Self],
+        a: &RU256,
+        b: &RU256,
+        curve: &T,
+    ) -> Self {
+        /*
+         * R = 0
+         * LOOP: R = (R * 16) + table_p[a[i]] + table_q[b[i]]
+         * Note: a[i], b[i] are 4 bit windows, i starts from 63 and goes down up until 0 (inclusive)
+         */
+        let mut r = Self::zero_point();
+        let mask = U256::from(0xf);
+
+        for i in (0..64).rev() {
+            for _ in 0..4 {
+                r = r.double(curve);
+            }
+
+            let window_a = ((a.v >> (i * 4)) & mask).low_u64() as usize;
+            let window_b = ((b.v >> (i * 4)) & mask).low_u64() as usize;
+            r = r
+                .add(&table_p[window_a], curve)
+                .add(&table_q[window_b], curve);
+        }
+
+        r
+    }
+
+    // Σ scalars[i] * points[i] with one shared chain of doublings (Straus),
+    // using a 4 bit window table per point
+    pub fn multi_scalar_multiplication<T: SECP256>(
+        points: &[Self],
+        scalars: &[RU256],
+        curve: &T,
+    ) -> Self {
+        assert_eq!(points.len(), scalars.len(), "one scalar per point");
+
+        let tables: Vec<Vec<Self>> = points.iter().map(|p| p.window_table(curve)).collect();
+        let mask = U256::from(0xf);
+        let mut r = Self::zero_point();
+
+        for i in (0..64).rev() {
+            for _ in 0..4 {
+                r = r.double(curve);
+            }
+
+            for (table, scalar) in tables.iter().zip(scalars) {
+                let window = ((scalar.v >> (i * 4)) & mask).low_u64() as usize;
+                r = r.add(&table[window], curve);
+            }
+        }
+
+        r
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::JacobianPoint;
     use crate::{
-        curves::{r1::R1, SECP256},
+        curves::{k1::K1, r1::R1, SECP256},
         ecmaths::ru256::RU256,
     };
+    use std::str::FromStr;
 
     #[test]
     fn test_shamir() {
         let curve = R1;
         let p = R1::g().multiply(&RU256::two(), &curve).to_jacobian();
-        let q = R1::g().multiply(&&RU256::four(), &curve).to_jacobian();
+        let q = R1::g().multiply(&RU256::four(), &curve).to_jacobian();
         let a = RU256::three();
         let b = RU256::eight();
 
@@ -229,4 +301,60 @@ mod tests {
 
         assert_eq!(r, s);
     }
+
+    #[test]
+    fn test_windowed_shamir() {
+        let curve = K1;
+        let p = K1::g().to_jacobian();
+        let q = K1::g().multiply(&RU256::three(), &curve).to_jacobian();
+        let a =
+            RU256::from_str("0xf0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899aabbccddeeff")
+                .unwrap();
+        let b =
+            RU256::from_str("0x0f1e2d3c4b5a69788796a5b4c3d2e1f0ffeeddccbbaa99887766554433221100")
+                .unwrap();
+
+        let table_p = p.window_table(&curve);
+        let table_q = q.window_table(&curve);
+        assert_eq!(
+            table_p[15].from_jacobian(&curve),
+            p.multiply(&RU256::from_str("0xf").unwrap(), &curve)
+                .from_jacobian(&curve)
+        );
+
+        let r = p
+            .strauss_shamir_multiplication(&q, &a, &b, &curve)
+            .from_jacobian(&curve);
+        let s = JacobianPoint::windowed_strauss_shamir_multiplication(
+            &table_p, &table_q, &a, &b, &curve,
+        )
+        .from_jacobian(&curve);
+
+        assert_eq!(r, s);
+    }
+
+    #[test]
+    fn test_multi_scalar_multiplication() {
+        let curve = R1;
+        let points: Vec<JacobianPoint> = [RU256::one(), RU256::three(), RU256::eight()]
+            .iter()
+            .map(|k| R1::g().multiply(k, &curve).to_jacobian())
+            .collect();
+        let scalars = [
+            RU256::from_str("0x0123456789abcdef").unwrap(),
+            R1::n().sub_mod(&RU256::two(), &R1::n()),
+            RU256::zero(),
+        ];
+
+        // 0x0123456789abcdef * G + (n - 2) * 3G + 0 * 8G
+        let expected = R1::g().multiply(
+            &scalars[0].sub_mod(&RU256::from_str("0x6").unwrap(), &R1::n()),
+            &curve,
+        );
+        let r = JacobianPoint::multi_scalar_multiplication(&points, &scalars, &curve)
+            .from_jacobian(&curve);
+        assert_eq!(r, expected);
+
+        assert!(JacobianPoint::multi_scalar_multiplication(&[], &[], &curve).is_zero_point());
+    }
 }
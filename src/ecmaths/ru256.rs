@@ -1,33 +1,38 @@
 // modified from https://github.com/darth-cy/ecc/blob/main/src/ru256.rs
 // temp solution for starting development
 
-use crate::bytes;
+use crate::{bytes, error::Error};
 use hex;
 use primitive_types::U256;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
+use zeroize::Zeroize;
 
 #[derive(Debug, Clone, PartialOrd)]
 pub struct RU256 {
     pub v: U256,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct RU256ParseError;
-
 impl FromStr for RU256 {
-    type Err = RU256ParseError;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match U256::from_str_radix(s, 16) {
-            Ok(n) => return Ok(Self { v: n }),
-            Err(_) => return Err(RU256ParseError),
+            Ok(n) => Ok(Self { v: n }),
+            Err(_) => Err(Error::InvalidEncoding),
         }
     }
 }
-impl ToString for RU256 {
-    fn to_string(&self) -> String {
+impl fmt::Display for RU256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut bytes = [0; 32];
         self.v.to_big_endian(&mut bytes);
-        return hex::encode(bytes);
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+// lets secrets (private keys, nonces) be wiped, e.g. through zeroize::Zeroizing
+impl Zeroize for RU256 {
+    fn zeroize(&mut self) {
+        self.v.0.zeroize();
     }
 }
 
@@ -35,9 +40,9 @@ impl RU256 {
     pub fn from_bytes(bs: &[u8]) -> Self {
         assert!(bs.len() <= 32, "big-endian");
 
-        return Self {
+        Self {
             v: U256::from_big_endian(bs),
-        };
+        }
     }
     pub fn to_bytes(&self, r: &mut [u8]) {
         self.v.to_big_endian(r);
@@ -50,22 +55,22 @@ impl RU256 {
 
     // constants
     pub fn zero() -> Self {
-        return Self::from_str("0x0").unwrap();
+        Self::from_str("0x0").unwrap()
     }
     pub fn one() -> Self {
-        return Self::from_str("0x1").unwrap();
+        Self::from_str("0x1").unwrap()
     }
     pub fn two() -> Self {
-        return Self::from_str("0x2").unwrap();
+        Self::from_str("0x2").unwrap()
     }
     pub fn three() -> Self {
-        return Self::from_str("0x3").unwrap();
+        Self::from_str("0x3").unwrap()
     }
     pub fn four() -> Self {
-        return Self::from_str("0x4").unwrap();
+        Self::from_str("0x4").unwrap()
     }
     pub fn eight() -> Self {
-        return Self::from_str("0x8").unwrap();
+        Self::from_str("0x8").unwrap()
     }
 
     // ******************************************************************
@@ -92,7 +97,7 @@ impl RU256 {
 
         x3 = x3.checked_rem(p.v).expect("modulo");
 
-        return Self { v: x3 };
+        Self { v: x3 }
     }
 
     // ******************************************************************
@@ -104,7 +109,7 @@ impl RU256 {
         let x1 = self.v.checked_rem(p.v).expect("modulo");
         let x2 = b.v.checked_rem(p.v).expect("modulo");
 
-        return Self { v: x1 }.add_mod(&Self { v: (p.v - x2) }, p);
+        Self { v: x1 }.add_mod(&Self { v: (p.v - x2) }, p)
     }
 
     // ******************************************************************
@@ -155,7 +160,7 @@ impl RU256 {
             }
         }
 
-        return base;
+        base
     }
 
     // ******************************************************************
@@ -189,7 +194,7 @@ impl RU256 {
             }
         }
 
-        return base;
+        base
     }
 
     // ******************************************************************
@@ -201,13 +206,13 @@ impl RU256 {
     // ******************************************************************
     pub fn div_mod(&self, b: &Self, p: &Self) -> Self {
         assert!(p.v - 2 > U256::from_big_endian(&[0]));
-        return self.mul_mod(&b.exp_mod(&RU256 { v: p.v - 2 }, p), p);
+        self.mul_mod(&b.exp_mod(&RU256 { v: p.v - 2 }, p), p)
     }
 }
 
 impl PartialEq for RU256 {
     fn eq(&self, other: &Self) -> bool {
-        return self.v == other.v;
+        self.v == other.v
     }
 }
 
@@ -0,0 +1,79 @@
+use crate::ethereum::{eip712::Eip712Error, rlp::RlpError, transaction::TransactionError};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // the recovery id (v) is missing or not one the operation accepts
+    InvalidV,
+    // r is not the x coordinate of a point on the curve
+    RNotOnCurve,
+    // a scalar that has to be invertible is 0 mod n
+    ZeroScalar,
+    // a scalar or coordinate is outside of its allowed range
+    OutOfRange,
+    // malformed hex, bytes or serialization
+    InvalidEncoding,
+    PointNotOnCurve,
+    PointAtInfinity,
+    // s is in the upper half of n where only low s signatures are accepted
+    HighS,
+    // well formed but does not verify against the message and key
+    InvalidSignature,
+    // the OS random number generator failed
+    RandomSource,
+    // a key that is not part of the key set or secret nonce it is used with
+    UnknownKey,
+    // a hardened child can only be derived from a private key
+    HardenedDerivation,
+    // the ciphertext or its associated data fails authentication
+    Decryption,
+    // the cipher rejected the message, e.g. one over the AES-GCM length limit
+    Encryption,
+    Rlp(RlpError),
+    Eip712(Eip712Error),
+    Transaction(TransactionError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidV => write!(f, "invalid v"),
+            Self::RNotOnCurve => write!(f, "r cannot be x coordinate of a point of the curve"),
+            Self::ZeroScalar => write!(f, "scalar is 0 mod n"),
+            Self::OutOfRange => write!(f, "value out of range"),
+            Self::InvalidEncoding => write!(f, "invalid encoding"),
+            Self::PointNotOnCurve => write!(f, "point is not on the curve"),
+            Self::PointAtInfinity => write!(f, "point at infinity"),
+            Self::HighS => write!(f, "s is not in the lower half of n"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::RandomSource => write!(f, "os random number generator failed"),
+            Self::UnknownKey => write!(f, "unknown key"),
+            Self::HardenedDerivation => write!(f, "hardened derivation from a public key"),
+            Self::Decryption => write!(f, "decryption failed"),
+            Self::Encryption => write!(f, "encryption failed"),
+            Self::Rlp(err) => write!(f, "rlp: {}", err),
+            Self::Eip712(err) => write!(f, "eip712: {}", err),
+            Self::Transaction(err) => write!(f, "transaction: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<RlpError> for Error {
+    fn from(err: RlpError) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+impl From<Eip712Error> for Error {
+    fn from(err: Eip712Error) -> Self {
+        Self::Eip712(err)
+    }
+}
+
+impl From<TransactionError> for Error {
+    fn from(err: TransactionError) -> Self {
+        Self::Transaction(err)
+    }
+}
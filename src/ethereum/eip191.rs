@@ -0,0 +1,95 @@
+use crate::{
+    curves::k1::K1,
+    ecmaths::{affine::ECAffinePoint, ru256::RU256},
+    error::Error,
+    hash::keccak256,
+    signature::{PrivateKey, Signature},
+};
+
+// ******************************************************************
+// EIP-191 Signed Data
+// 0x19 <1 byte version> <version specific data> <data to sign>
+// Reference: https://eips.ethereum.org/EIPS/eip-191
+// ******************************************************************
+
+const PERSONAL_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+// version 0x45 (E): personal_sign / eth_sign
+pub fn hash_message(message: &[u8]) -> [u8; 32] {
+    let mut data = PERSONAL_MESSAGE_PREFIX.to_vec();
+    data.extend_from_slice(message.len().to_string().as_bytes());
+    data.extend_from_slice(message);
+
+    keccak256(&data)
+}
+
+// version 0x00: data with intended validator
+pub fn hash_with_validator(validator: &[u8; 20], data: &[u8]) -> [u8; 32] {
+    let mut encoded = vec![0x19, 0x00];
+    encoded.extend_from_slice(validator);
+    encoded.extend_from_slice(data);
+
+    keccak256(&encoded)
+}
+
+impl PrivateKey {
+    pub fn sign_message(&self, message: &[u8], nonce: &RU256) -> Signature {
+        self.raw_sign(&RU256::from_bytes(&hash_message(message)), nonce, &K1)
+    }
+}
+
+impl Signature {
+    pub fn recover_message(&self, message: &[u8]) -> Result<ECAffinePoint, Error> {
+        self.clone()
+            .raw_recover(&RU256::from_bytes(&hash_message(message)), &K1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_message, hash_with_validator};
+    use crate::{
+        curves::k1::K1, ecmaths::ru256::RU256, ethereum::address_from_pub_key, hash::keccak256,
+        signature::PrivateKey,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_hash_message() {
+        assert_eq!(
+            hex::encode(hash_message(b"Hello World")),
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+        assert_eq!(
+            hex::encode(hash_message(b"")),
+            "5f35dce98ba4fba25530a026ed80b2cecdaa31091ba4958b99b52ea1d068adad"
+        );
+    }
+
+    #[test]
+    fn test_hash_with_validator() {
+        let validator = [0x11; 20];
+        let mut expected = vec![0x19, 0x00];
+        expected.extend_from_slice(&validator);
+        expected.extend_from_slice(b"data");
+
+        assert_eq!(
+            hash_with_validator(&validator, b"data"),
+            keccak256(&expected)
+        );
+    }
+
+    #[test]
+    fn test_sign_and_recover_message() {
+        let priv_key = PrivateKey::new(RU256::from_bytes(&keccak256(b"cow")));
+        let pub_key = priv_key.to_pub_key(&K1);
+        assert_eq!(
+            hex::encode(address_from_pub_key(&pub_key)),
+            "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+
+        let signature = priv_key.sign_message(b"Hello World", &RU256::from_str("0x1234").unwrap());
+        assert_eq!(signature.recover_message(b"Hello World").unwrap(), pub_key);
+        assert_ne!(signature.recover_message(b"Hello World!").unwrap(), pub_key);
+    }
+}
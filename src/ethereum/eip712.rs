@@ -0,0 +1,474 @@
+use crate::{
+    curves::k1::K1,
+    ecmaths::{affine::ECAffinePoint, ru256::RU256},
+    error::Error,
+    hash::keccak256,
+    signature::{PrivateKey, Signature},
+};
+use primitive_types::U256;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+// ******************************************************************
+// EIP-712 Typed Structured Data Hashing
+// signing hash = keccak256(0x19 || 0x01 || domainSeparator || hashStruct(message))
+// Reference: https://eips.ethereum.org/EIPS/eip-712
+// ******************************************************************
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Eip712Error {
+    InvalidJson,
+    UnknownType(String),
+    InvalidValue(String),
+}
+
+impl fmt::Display for Eip712Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson => write!(f, "invalid typed data json"),
+            Self::UnknownType(name) => write!(f, "unknown type {}", name),
+            Self::InvalidValue(value) => write!(f, "invalid value {}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedDataField {
+    pub name: String,
+    pub field_type: String,
+}
+
+// Mirrors the JSON accepted by eth_signTypedData_v4
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl FromStr for TypedData {
+    type Err = Eip712Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json: Value = serde_json::from_str(s).map_err(|_| Eip712Error::InvalidJson)?;
+
+        let mut types = BTreeMap::new();
+        for (name, fields) in json["types"].as_object().ok_or(Eip712Error::InvalidJson)? {
+            let fields = fields
+                .as_array()
+                .ok_or(Eip712Error::InvalidJson)?
+                .iter()
+                .map(
+                    |field| match (field["name"].as_str(), field["type"].as_str()) {
+                        (Some(name), Some(field_type)) => Ok(TypedDataField {
+                            name: name.to_string(),
+                            field_type: field_type.to_string(),
+                        }),
+                        _ => Err(Eip712Error::InvalidJson),
+                    },
+                )
+                .collect::<Result<Vec<_>, _>>()?;
+            types.insert(name.clone(), fields);
+        }
+
+        Ok(Self {
+            types,
+            primary_type: json["primaryType"]
+                .as_str()
+                .ok_or(Eip712Error::InvalidJson)?
+                .to_string(),
+            domain: json["domain"].clone(),
+            message: json["message"].clone(),
+        })
+    }
+}
+
+impl TypedData {
+    // encodeType = Name(type1 name1,...,typeN nameN) followed by all referenced
+    // struct types sorted by name
+    pub fn encode_type(&self, primary_type: &str) -> Result<String, Eip712Error> {
+        let mut deps = BTreeSet::new();
+        self.collect_dependencies(primary_type, &mut deps)?;
+        deps.remove(primary_type);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(primary_type).chain(deps.iter().map(|d| d.as_str())) {
+            let fields = self.struct_fields(name)?;
+            let members = fields
+                .iter()
+                .map(|f| format!("{} {}", f.field_type, f.name))
+                .collect::<Vec<_>>()
+                .join(",");
+            encoded.push_str(&format!("{}({})", name, members));
+        }
+
+        Ok(encoded)
+    }
+
+    pub fn type_hash(&self, primary_type: &str) -> Result<[u8; 32], Eip712Error> {
+        Ok(keccak256(self.encode_type(primary_type)?.as_bytes()))
+    }
+
+    // encodeData = enc(value1) || ... || enc(valueN), each 32 bytes
+    pub fn encode_data(&self, primary_type: &str, data: &Value) -> Result<Vec<u8>, Eip712Error> {
+        let mut encoded = self.type_hash(primary_type)?.to_vec();
+        for field in self.struct_fields(primary_type)? {
+            let value = data
+                .get(&field.name)
+                .ok_or_else(|| Eip712Error::InvalidValue(field.name.clone()))?;
+            encoded.extend_from_slice(&self.encode_value(&field.field_type, value)?);
+        }
+
+        Ok(encoded)
+    }
+
+    pub fn hash_struct(&self, primary_type: &str, data: &Value) -> Result<[u8; 32], Eip712Error> {
+        Ok(keccak256(&self.encode_data(primary_type, data)?))
+    }
+
+    pub fn domain_separator(&self) -> Result<[u8; 32], Eip712Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    pub fn signing_hash(&self) -> Result<[u8; 32], Eip712Error> {
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend_from_slice(&self.domain_separator()?);
+        encoded.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+
+        Ok(keccak256(&encoded))
+    }
+
+    fn struct_fields(&self, name: &str) -> Result<&Vec<TypedDataField>, Eip712Error> {
+        self.types
+            .get(name)
+            .ok_or_else(|| Eip712Error::UnknownType(name.to_string()))
+    }
+
+    fn collect_dependencies(
+        &self,
+        name: &str,
+        deps: &mut BTreeSet<String>,
+    ) -> Result<(), Eip712Error> {
+        if deps.contains(name) {
+            return Ok(());
+        }
+        deps.insert(name.to_string());
+
+        for field in self.struct_fields(name)? {
+            let base_type = field.field_type.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) {
+                self.collect_dependencies(base_type, deps)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_value(&self, field_type: &str, value: &Value) -> Result<[u8; 32], Eip712Error> {
+        let invalid = || Eip712Error::InvalidValue(format!("{}: {}", field_type, value));
+
+        // arrays are encoded as the keccak256 of their concatenated encoded elements
+        if let Some(inner_type) = field_type.strip_suffix(']') {
+            let (inner_type, length) = inner_type.rsplit_once('[').ok_or_else(invalid)?;
+            let items = value.as_array().ok_or_else(invalid)?;
+            if !length.is_empty() && length.parse::<usize>() != Ok(items.len()) {
+                return Err(invalid());
+            }
+
+            let mut encoded = vec![];
+            for item in items {
+                encoded.extend_from_slice(&self.encode_value(inner_type, item)?);
+            }
+            return Ok(keccak256(&encoded));
+        }
+
+        if self.types.contains_key(field_type) {
+            return self.hash_struct(field_type, value);
+        }
+
+        let mut word = [0; 32];
+        match field_type {
+            "string" => word = keccak256(value.as_str().ok_or_else(invalid)?.as_bytes()),
+            "bytes" => word = keccak256(&parse_hex(value).ok_or_else(invalid)?),
+            "bool" => word[31] = value.as_bool().ok_or_else(invalid)? as u8,
+            "address" => {
+                let address = parse_hex(value).ok_or_else(invalid)?;
+                if address.len() != 20 {
+                    return Err(invalid());
+                }
+                word[12..].copy_from_slice(&address);
+            }
+            _ => {
+                if let Some(size) = field_type.strip_prefix("bytes") {
+                    let size = parse_size(size, 32).ok_or_else(invalid)?;
+                    let bytes = parse_hex(value).ok_or_else(invalid)?;
+                    // bytesN is exactly N bytes, never implicitly padded
+                    if bytes.len() != size {
+                        return Err(invalid());
+                    }
+                    word[..size].copy_from_slice(&bytes);
+                } else if let Some(bits) = field_type.strip_prefix("uint") {
+                    let bits = parse_size(bits, 256).ok_or_else(invalid)?;
+                    let (negative, v) = parse_integer(value).ok_or_else(invalid)?;
+                    if negative || (bits < 256 && v >> bits != U256::zero()) {
+                        return Err(invalid());
+                    }
+                    v.to_big_endian(&mut word);
+                } else if let Some(bits) = field_type.strip_prefix("int") {
+                    let bits = parse_size(bits, 256).ok_or_else(invalid)?;
+                    let (negative, v) = parse_integer(value).ok_or_else(invalid)?;
+
+                    // -2^(bits - 1) <= value < 2^(bits - 1)
+                    let limit = U256::one() << (bits - 1);
+                    if (negative && v > limit) || (!negative && v >= limit) {
+                        return Err(invalid());
+                    }
+                    match negative {
+                        true => (!v).overflowing_add(U256::one()).0,
+                        false => v,
+                    }
+                    .to_big_endian(&mut word);
+                } else {
+                    return Err(Eip712Error::UnknownType(field_type.to_string()));
+                }
+            }
+        }
+
+        Ok(word)
+    }
+}
+
+impl PrivateKey {
+    pub fn sign_typed_data(
+        &self,
+        typed_data: &TypedData,
+        nonce: &RU256,
+    ) -> Result<Signature, Error> {
+        Ok(self.raw_sign(&RU256::from_bytes(&typed_data.signing_hash()?), nonce, &K1))
+    }
+}
+
+impl Signature {
+    pub fn recover_typed_data(&self, typed_data: &TypedData) -> Result<ECAffinePoint, Error> {
+        self.clone()
+            .raw_recover(&RU256::from_bytes(&typed_data.signing_hash()?), &K1)
+    }
+}
+
+// sizes are multiples of 8 for intN/uintN and 1..=32 for bytesN
+fn parse_size(size: &str, max: usize) -> Option<usize> {
+    let size = size.parse::<usize>().ok()?;
+    match size > 0 && size <= max && (max == 32 || size % 8 == 0) {
+        true => Some(size),
+        false => None,
+    }
+}
+
+fn parse_hex(value: &Value) -> Option<Vec<u8>> {
+    let s = value.as_str()?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
+
+// integers may be json numbers or decimal / 0x-prefixed hex strings
+fn parse_integer(value: &Value) -> Option<(bool, U256)> {
+    match value {
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(v), _) => Some((false, U256::from(v))),
+            (None, Some(v)) => Some((true, U256::from(v.unsigned_abs()))),
+            _ => None,
+        },
+        Value::String(s) => {
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s.as_str()),
+            };
+            let v = match s.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok()?,
+                None => U256::from_dec_str(s).ok()?,
+            };
+            Some((negative && !v.is_zero(), v))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Eip712Error, TypedData};
+    use crate::{
+        curves::k1::K1, ecmaths::ru256::RU256, error::Error, ethereum::address_from_pub_key,
+        hash::keccak256, signature::PrivateKey,
+    };
+    use std::str::FromStr;
+
+    // Example from https://eips.ethereum.org/assets/eip-712/Example.js
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn test_eip712_example() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(typed_data.type_hash("Mail").unwrap()),
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(typed_data.domain_separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(typed_data.signing_hash().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        let priv_key = PrivateKey::new(RU256::from_bytes(&keccak256(b"cow")));
+
+        let signature = priv_key
+            .sign_typed_data(&typed_data, &RU256::from_str("0x0b").unwrap())
+            .unwrap();
+
+        let signer = signature.recover_typed_data(&typed_data).unwrap();
+        assert_eq!(signer, priv_key.to_pub_key(&K1));
+        // the private key of the example's "from" wallet is keccak256("cow")
+        assert_eq!(
+            hex::encode(address_from_pub_key(&signer)),
+            "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+
+        let mut unknown = typed_data.clone();
+        unknown.primary_type = "Missing".to_string();
+        assert_eq!(
+            priv_key
+                .sign_typed_data(&unknown, &RU256::from_str("0x0b").unwrap())
+                .err(),
+            Some(Error::Eip712(Eip712Error::UnknownType(
+                "Missing".to_string()
+            )))
+        );
+        assert_eq!(
+            signature.recover_typed_data(&unknown).err(),
+            Some(Error::Eip712(Eip712Error::UnknownType(
+                "Missing".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_encode_arrays_and_integers() {
+        let typed_data = TypedData::from_str(
+            r#"{
+                "types": {
+                    "EIP712Domain": [{ "name": "chainId", "type": "uint256" }],
+                    "Group": [
+                        { "name": "ids", "type": "int8[2]" },
+                        { "name": "tags", "type": "bytes4[]" }
+                    ]
+                },
+                "primaryType": "Group",
+                "domain": { "chainId": "0x01" },
+                "message": { "ids": [-1, "127"], "tags": ["0xdeadbeef"] }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            typed_data.encode_type("Group").unwrap(),
+            "Group(int8[2] ids,bytes4[] tags)"
+        );
+
+        let mut ids = [0xff; 64];
+        ids[32..63].copy_from_slice(&[0; 31]);
+        ids[63] = 0x7f;
+        let mut tags = [0; 32];
+        tags[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let encoded = typed_data
+            .encode_data("Group", &typed_data.message)
+            .unwrap();
+        assert_eq!(&encoded[32..64], &keccak256(&ids));
+        assert_eq!(&encoded[64..96], &keccak256(&tags));
+
+        for tag in ["0xdead", "0xdeadbeef00"] {
+            assert!(matches!(
+                typed_data.encode_data(
+                    "Group",
+                    &serde_json::json!({ "ids": [0, 0], "tags": [tag] })
+                ),
+                Err(Eip712Error::InvalidValue(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid_typed_data() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+
+        assert_eq!(
+            typed_data.type_hash("Missing"),
+            Err(Eip712Error::UnknownType("Missing".to_string()))
+        );
+        assert!(matches!(
+            typed_data.encode_data("Person", &serde_json::json!({ "name": "Cow" })),
+            Err(Eip712Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            typed_data.encode_data(
+                "Person",
+                &serde_json::json!({ "name": "Cow", "wallet": "0x1234" })
+            ),
+            Err(Eip712Error::InvalidValue(_))
+        ));
+        assert_eq!(
+            TypedData::from_str("{}").unwrap_err(),
+            Eip712Error::InvalidJson
+        );
+    }
+}
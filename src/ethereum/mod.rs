@@ -0,0 +1,41 @@
+use crate::{
+    curves::k1::K1, ecmaths::affine::ECAffinePoint, hash::keccak256,
+    signature::public_key::PublicKey,
+};
+
+pub mod eip191;
+pub mod eip712;
+pub mod precompiles;
+pub mod rlp;
+pub mod transaction;
+
+// address = last 20 bytes of keccak256(x || y)
+pub fn address_from_pub_key(pub_key: &ECAffinePoint) -> [u8; 20] {
+    let mut encoded = [0; 64];
+    pub_key.x.to_bytes(&mut encoded[..32]);
+    pub_key.y.to_bytes(&mut encoded[32..]);
+
+    let mut address = [0; 20];
+    address.copy_from_slice(&keccak256(&encoded)[12..]);
+    address
+}
+
+impl PublicKey<K1> {
+    pub fn to_address(&self) -> [u8; 20] {
+        address_from_pub_key(self.point())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::k1::K1, ecmaths::ru256::RU256, signature::PrivateKey};
+
+    #[test]
+    fn test_public_key_address() {
+        let pub_key = PrivateKey::new(RU256::one()).public_key(&K1).unwrap();
+        assert_eq!(
+            hex::encode(pub_key.to_address()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+    }
+}
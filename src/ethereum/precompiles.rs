@@ -0,0 +1,333 @@
+use super::address_from_pub_key;
+use crate::{
+    curves::{k1::K1, r1::R1, SECP256},
+    ecmaths::{affine::ECAffinePoint, ru256::RU256},
+    signature::{recovery_id::RecoveryId, Signature},
+};
+use std::str::FromStr;
+
+// ******************************************************************
+// EVM Precompiles
+// `None` is returned wherever the precompile returns empty output
+// ******************************************************************
+
+// ecrecover (0x01)
+// input: hash || v || r || s, each a 32 byte word
+// output: the signer address left padded to 32 bytes
+pub fn ecrecover(input: &[u8; 128]) -> Option<[u8; 32]> {
+    let msg_hash = RU256::from_bytes(&input[..32]);
+    let v = RU256::from_bytes(&input[32..64]);
+    let r = RU256::from_bytes(&input[64..96]);
+    let s = RU256::from_bytes(&input[96..]);
+
+    // v must be exactly 27 or 28 over the whole word, r and s in [1, n - 1]
+    if v != RU256::from_str("0x1b").unwrap() && v != RU256::from_str("0x1c").unwrap() {
+        return None;
+    }
+    let n = &K1::n();
+    if r == RU256::zero() || &r >= n || s == RU256::zero() || &s >= n {
+        return None;
+    }
+
+    let signature = Signature {
+        r,
+        s,
+        recovery_id: Some(RecoveryId::from_legacy(v.v.low_u64() as u8).ok()?),
+    };
+    let pub_key = signature.raw_recover(&msg_hash, &K1).ok()?;
+
+    let mut output = [0; 32];
+    output[12..].copy_from_slice(&address_from_pub_key(&pub_key));
+    Some(output)
+}
+
+// P256VERIFY (RIP-7212, 0x100)
+// input: hash || r || s || qx || qy, each a 32 byte word
+// output: 1 as a 32 byte word if the signature is valid
+// Reference: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub fn p256verify(input: &[u8; 160]) -> Option<[u8; 32]> {
+    let msg_hash = RU256::from_bytes(&input[..32]);
+    let r = RU256::from_bytes(&input[32..64]);
+    let s = RU256::from_bytes(&input[64..96]);
+    let pub_key = ECAffinePoint {
+        x: RU256::from_bytes(&input[96..128]),
+        y: RU256::from_bytes(&input[128..]),
+    };
+
+    // raw_verify checks that r and s are in [1, n - 1] and that the public key
+    // is a valid point that is not the identity
+    let signature = Signature {
+        r,
+        s,
+        recovery_id: None,
+    };
+    signature.raw_verify(&msg_hash, &pub_key, &R1).ok()?;
+
+    let mut output = [0; 32];
+    output[31] = 1;
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ecrecover, p256verify};
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::ru256::RU256,
+        ethereum::address_from_pub_key,
+        signature::PrivateKey,
+    };
+    use std::str::FromStr;
+
+    fn input(hex_input: &str) -> [u8; 128] {
+        hex::decode(hex_input).unwrap().try_into().unwrap()
+    }
+
+    // Vectors from go-ethereum core/vm/testdata/precompiles/ecRecover.json
+    const VALID_KEY: &str = "18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c000000000000000000000000000000000000000000000000000000000000001c73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75feeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549";
+
+    #[test]
+    fn test_ecrecover_valid_key() {
+        assert_eq!(
+            hex::encode(ecrecover(&input(VALID_KEY)).unwrap()),
+            "000000000000000000000000a94f5374fce5edbc8e2a8697c15331677e6ebf0b"
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_unrecoverable_key() {
+        let unrecoverable = "a8b53bdf3306a35a7103ab5504a0c9b492295564b6202b1942a84ef300107281000000000000000000000000000000000000000000000000000000000000001b30783565316530336635336365313862373732636362303039336666373166336635336635633735623734646362333161383561613862383839326234653862";
+
+        assert_eq!(ecrecover(&input(unrecoverable)), None);
+    }
+
+    #[test]
+    fn test_ecrecover_invalid_high_v_bits() {
+        for i in [32, 33, 62] {
+            let mut invalid = input(VALID_KEY);
+            invalid[i] = 0x01;
+
+            assert_eq!(ecrecover(&invalid), None, "byte {}", i);
+        }
+    }
+
+    #[test]
+    fn test_ecrecover_out_of_range() {
+        let n = K1::n();
+        let mut n_bytes = [0; 32];
+        n.to_bytes(&mut n_bytes);
+
+        let cases: [(usize, [u8; 32]); 5] = [
+            (32, [0; 32]),    // v = 0
+            (64, [0; 32]),    // r = 0
+            (96, [0; 32]),    // s = 0
+            (64, n_bytes),    // r = n
+            (96, [0xff; 32]), // s > n
+        ];
+        for (offset, word) in cases {
+            let mut invalid = input(VALID_KEY);
+            invalid[offset..offset + 32].copy_from_slice(&word);
+
+            assert_eq!(ecrecover(&invalid), None, "offset {}", offset);
+        }
+    }
+
+    #[test]
+    fn test_ecrecover_signature() {
+        let priv_key = PrivateKey::new(
+            RU256::from_str("0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8")
+                .unwrap(),
+        );
+        let msg_hash = RU256::from_str("0x0123456789").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x07").unwrap(), &K1);
+
+        let mut encoded = [0; 128];
+        msg_hash.to_bytes(&mut encoded[..32]);
+        encoded[63] = signature.recovery_id.unwrap().to_legacy();
+        signature.r.to_bytes(&mut encoded[64..96]);
+        signature.s.to_bytes(&mut encoded[96..]);
+
+        let output = ecrecover(&encoded).unwrap();
+        assert_eq!(output[..12], [0; 12]);
+        assert_eq!(
+            output[12..],
+            address_from_pub_key(&priv_key.to_pub_key(&K1))
+        );
+    }
+
+    // RFC 6979 A.2.5, P-256 with SHA-256 over "sample" and "test"
+    const P256_PUB_KEY: &str = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+    const P256_VECTORS: [&str; 2] = [
+        "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bfefd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+    ];
+
+    fn p256_input(vector: &str) -> [u8; 160] {
+        hex::decode(format!("{}{}", vector, P256_PUB_KEY))
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_p256verify_valid() {
+        let mut success = [0; 32];
+        success[31] = 1;
+        let n = &R1::n();
+
+        for vector in P256_VECTORS {
+            let input = p256_input(vector);
+            assert_eq!(p256verify(&input), Some(success));
+
+            // the precompile does not require low s
+            let mut malleated = input;
+            let s = RU256::from_bytes(&input[64..96]);
+            n.sub_mod(&s, n).to_bytes(&mut malleated[64..96]);
+            assert_eq!(p256verify(&malleated), Some(success));
+        }
+    }
+
+    // (name, input, valid) in the layout of go-ethereum
+    // core/vm/testdata/precompiles/p256Verify.json: the RIP-7212 example,
+    // Wycheproof ecdsa_secp256r1_sha256_p1363 tcId 1 (hash of "123400") and
+    // its special case words for r, s and the public key
+    const P256VERIFY_VECTORS: [(&str, &str, bool); 14] = [
+        (
+            "RIP-7212 example",
+            "4cee90eb86eaa050036147a12d49004b6b9c72bd725d39d4785011fe190f0b4da73bd4903f0ce3b639bbbf6e8e80d16931ff4bcf5993d58468e8fb19086e8cac36dbcd03009df8c59286b162af3bd7fcc0450c9aa81be5d10d312af6c66b1d604aebd3099c618202fcfe16ae7770b0c49ab5eadf74b754204a3bb6060e44eff37618b065f9832de4ca6ca971a7a1adc826d0f7c00181a5fb2ddf79ae00b4e10e",
+            true,
+        ),
+        (
+            "wycheproof #1: signature malleability",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            true,
+        ),
+        (
+            "wycheproof #1 with s = n - s",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e18b329f479a2bbd0a5c384ee1493b1f5186a87139cac5df4087c134b49156847db2927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            true,
+        ),
+        (
+            "wrong hash",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050222ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "r = 0",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca60502300000000000000000000000000000000000000000000000000000000000000004cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "s = 0",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e1800000000000000000000000000000000000000000000000000000000000000002927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "r = 0, s = 0",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "r = n",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc6325514cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "s = n",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e18ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc6325512927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "s = 2^256 - 1",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e18ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff2927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "negated public key",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c7328383887869a1553ff1b6de04eb6759f0b9f98994c2797affeaa72e568b18cbeaec1",
+            false,
+        ),
+        (
+            "public key off the curve",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd762927b10512bae3eddcfe467828128bad2903269919f7086069c8c4df6c732838c7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513f",
+            false,
+        ),
+        (
+            "qx = p",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd76ffffffff00000001000000000000000000000000ffffffffffffffffffffffffc7787964eaac00e5921fb1498a60f4606766b3d9685001558d1a974e7341513e",
+            false,
+        ),
+        (
+            "public key at infinity (0, 0)",
+            "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca6050232ba3a8be6b94d5ec80a6d9d1190a436effe50d85a1eee859b8cc6af9bd5c2e184cd60b855d442f5b3c7b11eb6c4e0ae7525fe710fab9aa7c77a67f79e6fadd7600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            false,
+        ),
+    ];
+
+    #[test]
+    fn test_p256verify_vectors() {
+        let mut success = [0; 32];
+        success[31] = 1;
+
+        for (name, input, valid) in P256VERIFY_VECTORS {
+            let input: [u8; 160] = hex::decode(input).unwrap().try_into().unwrap();
+            assert_eq!(p256verify(&input), valid.then_some(success), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_p256verify_raw_sign() {
+        let priv_key = PrivateKey::new(
+            RU256::from_str("0xc9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")
+                .unwrap(),
+        );
+        let msg_hash =
+            RU256::from_str("0xaf2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf")
+                .unwrap();
+        let nonce =
+            RU256::from_str("0xa6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60")
+                .unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &nonce, &R1);
+
+        // raw_sign returns the low s form of the RFC 6979 signature
+        let mut input = p256_input(P256_VECTORS[0]);
+        signature.s.to_bytes(&mut input[64..96]);
+        assert_eq!(input[32..64], p256_input(P256_VECTORS[0])[32..64]);
+        assert_eq!(
+            R1::n().sub_mod(&signature.s, &R1::n()),
+            RU256::from_bytes(&p256_input(P256_VECTORS[0])[64..96])
+        );
+        assert!(p256verify(&input).is_some());
+    }
+
+    #[test]
+    fn test_p256verify_invalid() {
+        let mut n_bytes = [0; 32];
+        R1::n().to_bytes(&mut n_bytes);
+        let mut p_bytes = [0; 32];
+        R1::p().to_bytes(&mut p_bytes);
+
+        let cases: [(usize, [u8; 32]); 9] = [
+            (0, [0x01; 32]),   // wrong hash
+            (32, [0; 32]),     // r = 0
+            (64, [0; 32]),     // s = 0
+            (32, n_bytes),     // r = n
+            (64, n_bytes),     // s = n
+            (64, [0xff; 32]),  // s > n
+            (96, p_bytes),     // qx = p
+            (128, [0x01; 32]), // q not on curve
+            (32, [0x01; 32]),  // wrong r
+        ];
+        for (offset, word) in cases {
+            let mut invalid = p256_input(P256_VECTORS[0]);
+            invalid[offset..offset + 32].copy_from_slice(&word);
+
+            assert_eq!(p256verify(&invalid), None, "offset {}", offset);
+        }
+
+        // the identity encoded as (0, 0)
+        let mut identity = p256_input(P256_VECTORS[0]);
+        identity[96..].copy_from_slice(&[0; 64]);
+        assert_eq!(p256verify(&identity), None);
+    }
+}
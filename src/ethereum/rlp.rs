@@ -0,0 +1,287 @@
+use crate::ecmaths::ru256::RU256;
+use primitive_types::U256;
+use std::fmt;
+
+// ******************************************************************
+// Recursive Length Prefix serialization
+// [0x00, 0x7f]  single byte, its own encoding
+// [0x80, 0xb7]  string of 0-55 bytes, 0x80 + len
+// [0xb8, 0xbf]  longer string, 0xb7 + len(len) followed by len
+// [0xc0, 0xf7]  list with 0-55 byte payload, 0xc0 + len
+// [0xf8, 0xff]  longer list, 0xf7 + len(len) followed by len
+// Reference: https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
+// ******************************************************************
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpError {
+    UnexpectedEnd,
+    TrailingBytes,
+    NonCanonical,
+    ExpectedBytes,
+    ExpectedList,
+    IntegerOverflow,
+}
+
+impl fmt::Display for RlpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::TrailingBytes => write!(f, "trailing bytes after the item"),
+            Self::NonCanonical => write!(f, "non canonical encoding"),
+            Self::ExpectedBytes => write!(f, "expected a string, found a list"),
+            Self::ExpectedList => write!(f, "expected a list, found a string"),
+            Self::IntegerOverflow => write!(f, "integer overflow"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpItem {
+    Bytes(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    // integers are encoded big-endian without leading zeros, 0 being the empty string
+    pub fn from_u256(v: &RU256) -> Self {
+        let mut bytes = [0; 32];
+        v.to_bytes(&mut bytes);
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(32);
+        Self::Bytes(bytes[start..].to_vec())
+    }
+
+    pub fn from_u64(v: u64) -> Self {
+        Self::from_u256(&RU256 { v: U256::from(v) })
+    }
+
+    pub fn as_bytes(&self) -> Result<&[u8], RlpError> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            Self::List(_) => Err(RlpError::ExpectedBytes),
+        }
+    }
+
+    pub fn as_list(&self) -> Result<&[RlpItem], RlpError> {
+        match self {
+            Self::List(items) => Ok(items),
+            Self::Bytes(_) => Err(RlpError::ExpectedList),
+        }
+    }
+
+    pub fn as_u256(&self) -> Result<RU256, RlpError> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > 32 {
+            return Err(RlpError::IntegerOverflow);
+        }
+        if bytes.first() == Some(&0) {
+            return Err(RlpError::NonCanonical);
+        }
+        Ok(RU256::from_bytes(bytes))
+    }
+
+    pub fn as_u64(&self) -> Result<u64, RlpError> {
+        let v = self.as_u256()?;
+        if v.v.bits() > 64 {
+            return Err(RlpError::IntegerOverflow);
+        }
+        Ok(v.v.low_u64())
+    }
+}
+
+pub fn encode(item: &RlpItem) -> Vec<u8> {
+    match item {
+        RlpItem::Bytes(bytes) => {
+            if bytes.len() == 1 && bytes[0] < 0x80 {
+                return bytes.clone();
+            }
+            let mut encoded = encode_length(bytes.len(), 0x80);
+            encoded.extend_from_slice(bytes);
+            encoded
+        }
+        RlpItem::List(items) => {
+            let payload: Vec<u8> = items.iter().flat_map(encode).collect();
+            let mut encoded = encode_length(payload.len(), 0xc0);
+            encoded.extend_from_slice(&payload);
+            encoded
+        }
+    }
+}
+
+fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len <= 55 {
+        return vec![offset + len as u8];
+    }
+
+    let len_bytes = len.to_be_bytes();
+    let start = len_bytes.iter().position(|b| *b != 0).unwrap_or(0);
+    let mut encoded = vec![offset + 55 + (len_bytes.len() - start) as u8];
+    encoded.extend_from_slice(&len_bytes[start..]);
+    encoded
+}
+
+// Decodes exactly one item, rejecting trailing data and non-canonical encodings
+pub fn decode(data: &[u8]) -> Result<RlpItem, RlpError> {
+    let (item, consumed) = decode_item(data)?;
+    if consumed != data.len() {
+        return Err(RlpError::TrailingBytes);
+    }
+    Ok(item)
+}
+
+fn decode_item(data: &[u8]) -> Result<(RlpItem, usize), RlpError> {
+    let prefix = *data.first().ok_or(RlpError::UnexpectedEnd)?;
+
+    match prefix {
+        0x00..=0x7f => Ok((RlpItem::Bytes(vec![prefix]), 1)),
+        0x80..=0xbf => {
+            let (offset, len) = decode_length(data, 0x80)?;
+            let bytes = payload(data, offset, len)?;
+            if len == 1 && bytes[0] < 0x80 {
+                return Err(RlpError::NonCanonical);
+            }
+            Ok((RlpItem::Bytes(bytes.to_vec()), offset + len))
+        }
+        0xc0..=0xff => {
+            let (offset, len) = decode_length(data, 0xc0)?;
+            let mut remaining = payload(data, offset, len)?;
+
+            let mut items = vec![];
+            while !remaining.is_empty() {
+                let (item, consumed) = decode_item(remaining)?;
+                items.push(item);
+                remaining = &remaining[consumed..];
+            }
+            Ok((RlpItem::List(items), offset + len))
+        }
+    }
+}
+
+fn payload(data: &[u8], offset: usize, len: usize) -> Result<&[u8], RlpError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(RlpError::UnexpectedEnd)
+}
+
+// returns (header length, payload length)
+fn decode_length(data: &[u8], offset: u8) -> Result<(usize, usize), RlpError> {
+    let short = data[0] - offset;
+    if short <= 55 {
+        return Ok((1, short as usize));
+    }
+
+    let len_of_len = (short - 55) as usize;
+    let len_bytes = data.get(1..1 + len_of_len).ok_or(RlpError::UnexpectedEnd)?;
+    if len_bytes[0] == 0 {
+        return Err(RlpError::NonCanonical);
+    }
+    if len_of_len > std::mem::size_of::<usize>() {
+        return Err(RlpError::IntegerOverflow);
+    }
+
+    let len = len_bytes
+        .iter()
+        .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+    if len <= 55 {
+        return Err(RlpError::NonCanonical);
+    }
+
+    Ok((1 + len_of_len, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, RlpError, RlpItem};
+    use crate::ecmaths::ru256::RU256;
+    use std::str::FromStr;
+
+    fn bytes(b: &[u8]) -> RlpItem {
+        RlpItem::Bytes(b.to_vec())
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let cases = [
+            (bytes(b"dog"), "83646f67"),
+            (
+                RlpItem::List(vec![bytes(b"cat"), bytes(b"dog")]),
+                "c88363617483646f67",
+            ),
+            (bytes(b""), "80"),
+            (RlpItem::List(vec![]), "c0"),
+            (RlpItem::from_u64(0), "80"),
+            (bytes(&[0x00]), "00"),
+            (RlpItem::from_u64(15), "0f"),
+            (RlpItem::from_u64(1024), "820400"),
+            (
+                // set theoretical representation of three
+                RlpItem::List(vec![
+                    RlpItem::List(vec![]),
+                    RlpItem::List(vec![RlpItem::List(vec![])]),
+                    RlpItem::List(vec![
+                        RlpItem::List(vec![]),
+                        RlpItem::List(vec![RlpItem::List(vec![])]),
+                    ]),
+                ]),
+                "c7c0c1c0c3c0c1c0",
+            ),
+            (
+                bytes(b"Lorem ipsum dolor sit amet, consectetur adipisicing elit"),
+                "b8384c6f72656d20697073756d20646f6c6f722073697420616d65742c20636f6e7365637465747572206164697069736963696e6720656c6974",
+            ),
+        ];
+
+        for (item, expected) in cases {
+            assert_eq!(hex::encode(encode(&item)), expected);
+            assert_eq!(decode(&hex::decode(expected).unwrap()).unwrap(), item);
+        }
+    }
+
+    #[test]
+    fn test_long_list() {
+        let item = RlpItem::List(vec![bytes(&[0xaa; 20]); 4]);
+        let encoded = encode(&item);
+
+        assert_eq!(&encoded[..2], &[0xf8, 84]);
+        assert_eq!(decode(&encoded).unwrap(), item);
+    }
+
+    #[test]
+    fn test_integers() {
+        let v = RU256::from_str("0x0100").unwrap();
+        let item = RlpItem::from_u256(&v);
+
+        assert_eq!(item, bytes(&[0x01, 0x00]));
+        assert_eq!(item.as_u256().unwrap(), v);
+        assert_eq!(item.as_u64().unwrap(), 256);
+        assert_eq!(bytes(&[0x00, 0x01]).as_u256(), Err(RlpError::NonCanonical));
+        assert_eq!(bytes(&[0x01; 9]).as_u64(), Err(RlpError::IntegerOverflow));
+        assert_eq!(
+            RlpItem::List(vec![]).as_u256(),
+            Err(RlpError::ExpectedBytes)
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let cases = [
+            ("", RlpError::UnexpectedEnd),
+            ("83646f", RlpError::UnexpectedEnd),
+            ("c883636174", RlpError::UnexpectedEnd),
+            ("8100", RlpError::NonCanonical),
+            ("b80100", RlpError::NonCanonical),
+            ("b90001aa", RlpError::NonCanonical),
+            ("f800", RlpError::NonCanonical),
+            ("83646f6700", RlpError::TrailingBytes),
+        ];
+
+        for (encoded, expected) in cases {
+            assert_eq!(
+                decode(&hex::decode(encoded).unwrap()),
+                Err(expected),
+                "{}",
+                encoded
+            );
+        }
+    }
+}
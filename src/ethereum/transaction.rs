@@ -0,0 +1,613 @@
+use super::{
+    address_from_pub_key,
+    rlp::{self, RlpError, RlpItem},
+};
+use crate::{
+    curves::k1::K1,
+    ecmaths::ru256::RU256,
+    error::Error,
+    hash::keccak256,
+    signature::{recovery_id::RecoveryId, PrivateKey, Signature},
+};
+use primitive_types::U256;
+use std::fmt;
+
+// ******************************************************************
+// Ethereum Transactions
+// legacy:   rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s])
+//           v = 27 + recid, or chainId * 2 + 35 + recid (EIP-155)
+// EIP-2930: 0x01 || rlp([chainId, nonce, gasPrice, gasLimit, to, value, data,
+//                        accessList, yParity, r, s])
+// EIP-1559: 0x02 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas,
+//                        gasLimit, to, value, data, accessList, yParity, r, s])
+// Reference: https://eips.ethereum.org/EIPS/eip-155
+//            https://eips.ethereum.org/EIPS/eip-2718
+//            https://eips.ethereum.org/EIPS/eip-2930
+//            https://eips.ethereum.org/EIPS/eip-1559
+// ******************************************************************
+
+const EIP2930_TX_TYPE: u8 = 0x01;
+const EIP1559_TX_TYPE: u8 = 0x02;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    UnsupportedType(u8),
+    InvalidFieldCount,
+    InvalidAddress,
+    InvalidStorageKey,
+    InvalidV,
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedType(tx_type) => write!(f, "unsupported type 0x{:02x}", tx_type),
+            Self::InvalidFieldCount => write!(f, "invalid number of fields"),
+            Self::InvalidAddress => write!(f, "address is not 20 bytes"),
+            Self::InvalidStorageKey => write!(f, "storage key is not 32 bytes"),
+            Self::InvalidV => write!(f, "invalid v"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessListItem {
+    pub address: [u8; 20],
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+// `chain_id: None` signs the pre EIP-155 payload that is replayable across chains
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegacyTransaction {
+    pub chain_id: Option<u64>,
+    pub nonce: RU256,
+    pub gas_price: RU256,
+    pub gas_limit: RU256,
+    pub to: Option<[u8; 20]>,
+    pub value: RU256,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eip2930Transaction {
+    pub chain_id: u64,
+    pub nonce: RU256,
+    pub gas_price: RU256,
+    pub gas_limit: RU256,
+    pub to: Option<[u8; 20]>,
+    pub value: RU256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: RU256,
+    pub max_priority_fee_per_gas: RU256,
+    pub max_fee_per_gas: RU256,
+    pub gas_limit: RU256,
+    pub to: Option<[u8; 20]>,
+    pub value: RU256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transaction {
+    Legacy(LegacyTransaction),
+    Eip2930(Eip2930Transaction),
+    Eip1559(Eip1559Transaction),
+}
+
+// the signature keeps the structured recovery id produced by `raw_sign`, the
+// transaction specific form of v is only applied when encoding
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedTransaction {
+    pub transaction: Transaction,
+    pub signature: Signature,
+}
+
+impl Transaction {
+    pub fn tx_type(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eip2930(_) => Some(EIP2930_TX_TYPE),
+            Self::Eip1559(_) => Some(EIP1559_TX_TYPE),
+        }
+    }
+
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut fields = self.fields();
+        if let Self::Legacy(LegacyTransaction {
+            chain_id: Some(chain_id),
+            ..
+        }) = self
+        {
+            fields.push(RlpItem::from_u64(*chain_id));
+            fields.push(RlpItem::from_u64(0));
+            fields.push(RlpItem::from_u64(0));
+        }
+
+        keccak256(&self.envelope(fields))
+    }
+
+    fn fields(&self) -> Vec<RlpItem> {
+        match self {
+            Self::Legacy(tx) => vec![
+                RlpItem::from_u256(&tx.nonce),
+                RlpItem::from_u256(&tx.gas_price),
+                RlpItem::from_u256(&tx.gas_limit),
+                encode_to(&tx.to),
+                RlpItem::from_u256(&tx.value),
+                RlpItem::Bytes(tx.data.clone()),
+            ],
+            Self::Eip2930(tx) => vec![
+                RlpItem::from_u64(tx.chain_id),
+                RlpItem::from_u256(&tx.nonce),
+                RlpItem::from_u256(&tx.gas_price),
+                RlpItem::from_u256(&tx.gas_limit),
+                encode_to(&tx.to),
+                RlpItem::from_u256(&tx.value),
+                RlpItem::Bytes(tx.data.clone()),
+                encode_access_list(&tx.access_list),
+            ],
+            Self::Eip1559(tx) => vec![
+                RlpItem::from_u64(tx.chain_id),
+                RlpItem::from_u256(&tx.nonce),
+                RlpItem::from_u256(&tx.max_priority_fee_per_gas),
+                RlpItem::from_u256(&tx.max_fee_per_gas),
+                RlpItem::from_u256(&tx.gas_limit),
+                encode_to(&tx.to),
+                RlpItem::from_u256(&tx.value),
+                RlpItem::Bytes(tx.data.clone()),
+                encode_access_list(&tx.access_list),
+            ],
+        }
+    }
+
+    // EIP-2718: typed transactions are prefixed with their type byte
+    fn envelope(&self, fields: Vec<RlpItem>) -> Vec<u8> {
+        let mut encoded = self.tx_type().map(|t| vec![t]).unwrap_or_default();
+        encoded.extend_from_slice(&rlp::encode(&RlpItem::List(fields)));
+        encoded
+    }
+}
+
+impl SignedTransaction {
+    // fails without a recovery id, or with one for R.x >= n that v / yParity
+    // cannot represent
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let recovery_id = self.signature.recovery_id.ok_or(Error::InvalidV)?;
+        let v = match &self.transaction {
+            Transaction::Legacy(LegacyTransaction {
+                chain_id: Some(chain_id),
+                ..
+            }) => recovery_id.to_eip155(*chain_id)?.v,
+            _ if recovery_id.x_reduced => return Err(Error::InvalidV),
+            Transaction::Legacy(_) => U256::from(recovery_id.to_legacy()),
+            _ => U256::from(recovery_id.to_byte()),
+        };
+
+        let mut fields = self.transaction.fields();
+        fields.push(RlpItem::from_u256(&RU256 { v }));
+        fields.push(RlpItem::from_u256(&self.signature.r));
+        fields.push(RlpItem::from_u256(&self.signature.s));
+
+        Ok(self.transaction.envelope(fields))
+    }
+
+    pub fn decode(raw: &[u8]) -> Result<Self, Error> {
+        let tx_type = *raw.first().ok_or(RlpError::UnexpectedEnd)?;
+
+        // a legacy transaction is an rlp list and so starts with a byte >= 0xc0
+        let (payload, field_count) = match tx_type {
+            0xc0..=0xff => (raw, 9),
+            EIP2930_TX_TYPE => (&raw[1..], 11),
+            EIP1559_TX_TYPE => (&raw[1..], 12),
+            _ => {
+                return Err(Error::Transaction(TransactionError::UnsupportedType(
+                    tx_type,
+                )))
+            }
+        };
+
+        let decoded = rlp::decode(payload)?;
+        let fields = decoded.as_list()?;
+        if fields.len() != field_count {
+            return Err(Error::Transaction(TransactionError::InvalidFieldCount));
+        }
+
+        let (transaction, recovery_id) = match tx_type {
+            EIP2930_TX_TYPE => (
+                Transaction::Eip2930(Eip2930Transaction {
+                    chain_id: fields[0].as_u64()?,
+                    nonce: fields[1].as_u256()?,
+                    gas_price: fields[2].as_u256()?,
+                    gas_limit: fields[3].as_u256()?,
+                    to: decode_to(&fields[4])?,
+                    value: fields[5].as_u256()?,
+                    data: fields[6].as_bytes()?.to_vec(),
+                    access_list: decode_access_list(&fields[7])?,
+                }),
+                y_parity(&fields[8])?,
+            ),
+            EIP1559_TX_TYPE => (
+                Transaction::Eip1559(Eip1559Transaction {
+                    chain_id: fields[0].as_u64()?,
+                    nonce: fields[1].as_u256()?,
+                    max_priority_fee_per_gas: fields[2].as_u256()?,
+                    max_fee_per_gas: fields[3].as_u256()?,
+                    gas_limit: fields[4].as_u256()?,
+                    to: decode_to(&fields[5])?,
+                    value: fields[6].as_u256()?,
+                    data: fields[7].as_bytes()?.to_vec(),
+                    access_list: decode_access_list(&fields[8])?,
+                }),
+                y_parity(&fields[9])?,
+            ),
+            _ => {
+                let v = fields[6].as_u256()?;
+                let (chain_id, recovery_id) = match v.v.low_u64() {
+                    27 | 28 if v.v.bits() <= 64 => {
+                        (None, RecoveryId::from_legacy(v.v.low_u64() as u8))
+                    }
+                    _ if v.v >= U256::from(35) => {
+                        let chain_id = (v.v - 35) / 2;
+                        if chain_id.bits() > 64 {
+                            return Err(Error::Transaction(TransactionError::InvalidV));
+                        }
+                        let chain_id = chain_id.low_u64();
+                        (Some(chain_id), RecoveryId::from_eip155(&v, chain_id))
+                    }
+                    _ => return Err(Error::Transaction(TransactionError::InvalidV)),
+                };
+
+                (
+                    Transaction::Legacy(LegacyTransaction {
+                        chain_id,
+                        nonce: fields[0].as_u256()?,
+                        gas_price: fields[1].as_u256()?,
+                        gas_limit: fields[2].as_u256()?,
+                        to: decode_to(&fields[3])?,
+                        value: fields[4].as_u256()?,
+                        data: fields[5].as_bytes()?.to_vec(),
+                    }),
+                    recovery_id.map_err(|_| Error::Transaction(TransactionError::InvalidV))?,
+                )
+            }
+        };
+
+        Ok(Self {
+            transaction,
+            signature: Signature {
+                r: fields[field_count - 2].as_u256()?,
+                s: fields[field_count - 1].as_u256()?,
+                recovery_id: Some(recovery_id),
+            },
+        })
+    }
+
+    // transaction hash, keccak256 of the signed encoding
+    pub fn hash(&self) -> Result<[u8; 32], Error> {
+        Ok(keccak256(&self.encode()?))
+    }
+
+    pub fn recover_sender(&self) -> Result<[u8; 20], Error> {
+        let msg_hash = RU256::from_bytes(&self.transaction.signing_hash());
+        Ok(address_from_pub_key(
+            &self.signature.clone().raw_recover(&msg_hash, &K1)?,
+        ))
+    }
+}
+
+impl PrivateKey {
+    pub fn sign_transaction(&self, transaction: &Transaction, nonce: &RU256) -> SignedTransaction {
+        let msg_hash = RU256::from_bytes(&transaction.signing_hash());
+
+        SignedTransaction {
+            transaction: transaction.clone(),
+            signature: self.raw_sign(&msg_hash, nonce, &K1),
+        }
+    }
+}
+
+pub fn recover_sender(raw: &[u8]) -> Result<[u8; 20], Error> {
+    SignedTransaction::decode(raw)?.recover_sender()
+}
+
+// typed transactions store the raw y parity, 0 or 1
+fn y_parity(item: &RlpItem) -> Result<RecoveryId, Error> {
+    let y_parity = item.as_u256()?;
+    if y_parity.v > U256::one() {
+        return Err(Error::Transaction(TransactionError::InvalidV));
+    }
+    Ok(RecoveryId::new(y_parity.v == U256::one(), false))
+}
+
+fn encode_to(to: &Option<[u8; 20]>) -> RlpItem {
+    RlpItem::Bytes(to.map(|a| a.to_vec()).unwrap_or_default())
+}
+
+// contract creations have an empty `to`
+fn decode_to(item: &RlpItem) -> Result<Option<[u8; 20]>, Error> {
+    let bytes = item.as_bytes()?;
+    match bytes.len() {
+        0 => Ok(None),
+        _ => Ok(Some(bytes.try_into().map_err(|_| {
+            Error::Transaction(TransactionError::InvalidAddress)
+        })?)),
+    }
+}
+
+fn encode_access_list(access_list: &[AccessListItem]) -> RlpItem {
+    RlpItem::List(
+        access_list
+            .iter()
+            .map(|item| {
+                RlpItem::List(vec![
+                    RlpItem::Bytes(item.address.to_vec()),
+                    RlpItem::List(
+                        item.storage_keys
+                            .iter()
+                            .map(|key| RlpItem::Bytes(key.to_vec()))
+                            .collect(),
+                    ),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn decode_access_list(item: &RlpItem) -> Result<Vec<AccessListItem>, Error> {
+    item.as_list()?
+        .iter()
+        .map(|entry| {
+            let entry = entry.as_list()?;
+            if entry.len() != 2 {
+                return Err(Error::Transaction(TransactionError::InvalidFieldCount));
+            }
+
+            Ok(AccessListItem {
+                address: entry[0]
+                    .as_bytes()?
+                    .try_into()
+                    .map_err(|_| Error::Transaction(TransactionError::InvalidAddress))?,
+                storage_keys: entry[1]
+                    .as_list()?
+                    .iter()
+                    .map(|key| {
+                        key.as_bytes()?
+                            .try_into()
+                            .map_err(|_| Error::Transaction(TransactionError::InvalidStorageKey))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        recover_sender, AccessListItem, Eip1559Transaction, Eip2930Transaction, LegacyTransaction,
+        SignedTransaction, Transaction, TransactionError,
+    };
+    use crate::{
+        curves::k1::K1,
+        ecmaths::ru256::RU256,
+        error::Error,
+        ethereum::{address_from_pub_key, rlp::RlpError},
+        signature::{recovery_id::RecoveryId, PrivateKey, Signature},
+    };
+    use std::str::FromStr;
+
+    fn eip155_example() -> LegacyTransaction {
+        LegacyTransaction {
+            chain_id: Some(1),
+            nonce: RU256::from_str("0x09").unwrap(),
+            gas_price: RU256::from_str("0x04a817c800").unwrap(),
+            gas_limit: RU256::from_str("0x5208").unwrap(),
+            to: Some([0x35; 20]),
+            value: RU256::from_str("0x0de0b6b3a7640000").unwrap(),
+            data: vec![],
+        }
+    }
+
+    // Example from https://eips.ethereum.org/EIPS/eip-155
+    #[test]
+    fn test_eip155_example() {
+        let raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        let transaction = Transaction::Legacy(eip155_example());
+
+        assert_eq!(
+            hex::encode(transaction.signing_hash()),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+
+        let signed = SignedTransaction::decode(&raw).unwrap();
+        assert_eq!(signed.transaction, transaction);
+        assert_eq!(
+            signed.signature.recovery_id,
+            Some(RecoveryId::new(false, false))
+        );
+        assert_eq!(signed.encode().unwrap(), raw);
+
+        let priv_key = PrivateKey::new(
+            RU256::from_str("0x4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap(),
+        );
+        let sender = address_from_pub_key(&priv_key.to_pub_key(&K1));
+        assert_eq!(
+            hex::encode(sender),
+            "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+        assert_eq!(recover_sender(&raw).unwrap(), sender);
+    }
+
+    // EIP-2930: go-ethereum core/types/transaction_test.go (signedEip2718Tx)
+    // EIP-1559: a mainnet transaction from alloy-consensus (test_decode_live_1559_tx)
+    // (raw, signing hash, hash, sender)
+    const TYPED_EXAMPLES: [(&str, &str, &str, &str); 2] = [
+        (
+            "01f8630103018261a894b94f5374fce5edbc8e2a8697c15331677e6ebf0b0a825544c001a0c9519f4f2b30335884581971573fadf60c6204f59a911df35ee8a540456b2660a032f1e8e2c5dd761f9e4f88f41c8310aeaba26a8bfcdacfedfa12ec3862d37521",
+            "49b486f0ec0a60dfbbca2d30cb07c9e8ffb2a2ff41f29a1ab6737475f6ff69f3",
+            "d900408d8fec1ffdb3e360685f94400b2ef6e1211ac0f98abbaa140e1a73683a",
+            "27cf7d8449c9da59189427619ba59f985cee9c0f",
+        ),
+        (
+            "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d495ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8",
+            "fb09c91c47c2717aadedeae95931a545a3d0d183c8c584325af02db1a85bfae8",
+            "ce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31",
+            "001e2b7de757ba469a57bf6b23d982458a07efce",
+        ),
+    ];
+
+    #[test]
+    fn test_typed_examples() {
+        for (raw, signing_hash, hash, sender) in TYPED_EXAMPLES {
+            let raw = hex::decode(raw).unwrap();
+            let signed = SignedTransaction::decode(&raw).unwrap();
+
+            assert_eq!(signed.transaction.tx_type(), Some(raw[0]));
+            assert_eq!(hex::encode(signed.transaction.signing_hash()), signing_hash);
+            assert_eq!(signed.encode().unwrap(), raw);
+            assert_eq!(hex::encode(signed.hash().unwrap()), hash);
+            assert_eq!(hex::encode(recover_sender(&raw).unwrap()), sender);
+        }
+
+        let signed = SignedTransaction::decode(&hex::decode(TYPED_EXAMPLES[0].0).unwrap()).unwrap();
+        assert_eq!(
+            signed.transaction,
+            Transaction::Eip2930(Eip2930Transaction {
+                chain_id: 1,
+                nonce: RU256::from_str("0x03").unwrap(),
+                gas_price: RU256::one(),
+                gas_limit: RU256::from_str("0x61a8").unwrap(),
+                to: Some(
+                    hex::decode("b94f5374fce5edbc8e2a8697c15331677e6ebf0b")
+                        .unwrap()
+                        .try_into()
+                        .unwrap()
+                ),
+                value: RU256::from_str("0x0a").unwrap(),
+                data: vec![0x55, 0x44],
+                access_list: vec![],
+            })
+        );
+        assert_eq!(
+            signed.signature.recovery_id,
+            Some(RecoveryId::new(true, false))
+        );
+    }
+
+    #[test]
+    fn test_sign_transactions() {
+        let priv_key = PrivateKey::new(
+            RU256::from_str("0x4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap(),
+        );
+        let sender = address_from_pub_key(&priv_key.to_pub_key(&K1));
+        let access_list = vec![AccessListItem {
+            address: [0xde; 20],
+            storage_keys: vec![[0x00; 32], [0x01; 32]],
+        }];
+
+        let transactions = [
+            Transaction::Legacy(LegacyTransaction {
+                chain_id: None,
+                ..eip155_example()
+            }),
+            Transaction::Legacy(LegacyTransaction {
+                chain_id: Some(11155111),
+                to: None,
+                data: vec![0x60, 0x80, 0x60, 0x40],
+                ..eip155_example()
+            }),
+            Transaction::Eip2930(Eip2930Transaction {
+                chain_id: 1,
+                nonce: RU256::zero(),
+                gas_price: RU256::from_str("0x3b9aca00").unwrap(),
+                gas_limit: RU256::from_str("0x7530").unwrap(),
+                to: Some([0x35; 20]),
+                value: RU256::zero(),
+                data: vec![0xa9, 0x05, 0x9c, 0xbb],
+                access_list: access_list.clone(),
+            }),
+            Transaction::Eip1559(Eip1559Transaction {
+                chain_id: 10,
+                nonce: RU256::from_str("0x2a").unwrap(),
+                max_priority_fee_per_gas: RU256::from_str("0x3b9aca00").unwrap(),
+                max_fee_per_gas: RU256::from_str("0x0ba43b7400").unwrap(),
+                gas_limit: RU256::from_str("0x5208").unwrap(),
+                to: Some([0x35; 20]),
+                value: RU256::from_str("0x0de0b6b3a7640000").unwrap(),
+                data: vec![],
+                access_list,
+            }),
+        ];
+
+        for (i, transaction) in transactions.iter().enumerate() {
+            let nonce = RU256::from_str(format!("0x0{}", i + 1).as_str()).unwrap();
+            let signed = priv_key.sign_transaction(transaction, &nonce);
+            let raw = signed.encode().unwrap();
+
+            if let Some(tx_type) = transaction.tx_type() {
+                assert_eq!(raw[0], tx_type);
+            }
+            assert_eq!(SignedTransaction::decode(&raw).unwrap(), signed);
+            assert_eq!(recover_sender(&raw).unwrap(), sender, "transaction {}", i);
+
+            // v / yParity has no encoding for these recovery ids
+            for recovery_id in [None, Some(RecoveryId::new(false, true))] {
+                let unencodable = SignedTransaction {
+                    transaction: transaction.clone(),
+                    signature: Signature {
+                        recovery_id,
+                        ..signed.signature.clone()
+                    },
+                };
+                assert_eq!(unencodable.encode(), Err(Error::InvalidV));
+                assert_eq!(unencodable.hash(), Err(Error::InvalidV));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert_eq!(
+            SignedTransaction::decode(&[]),
+            Err(Error::Rlp(RlpError::UnexpectedEnd))
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[0x02]),
+            Err(Error::Rlp(RlpError::UnexpectedEnd))
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[0x03, 0xc0]),
+            Err(Error::Transaction(TransactionError::UnsupportedType(0x03)))
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[0x03, 0xc0])
+                .unwrap_err()
+                .to_string(),
+            "transaction: unsupported type 0x03"
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[]).unwrap_err().to_string(),
+            "rlp: unexpected end of input"
+        );
+        assert_eq!(
+            SignedTransaction::decode(&[0x02, 0xc0]),
+            Err(Error::Transaction(TransactionError::InvalidFieldCount))
+        );
+
+        // v = 29 is neither pre nor post EIP-155
+        let mut raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        raw[43] = 0x1d;
+        assert_eq!(
+            SignedTransaction::decode(&raw),
+            Err(Error::Transaction(TransactionError::InvalidV))
+        );
+    }
+}
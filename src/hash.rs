@@ -0,0 +1,85 @@
+use ripemd::Ripemd160;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+// ripemd160(sha256(data)), the hash behind Bitcoin key fingerprints and addresses
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(sha256(data)).into()
+}
+
+// BIP-340 tagged hash: sha256(sha256(tag) || sha256(tag) || data)
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = sha256(tag.as_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for chunk in data {
+        hasher.update(chunk);
+    }
+    hasher.finalize().into()
+}
+
+// RFC 9380 expand_message_xmd with sha256, len has to be at most 255 * 32
+// Reference: https://www.rfc-editor.org/rfc/rfc9380#section-5.3.1
+pub fn expand_message_xmd(msg: &[&[u8]], dst: &[u8], len: usize) -> Vec<u8> {
+    /*
+     * b_0 = H(Z_pad || msg || I2OSP(len, 2) || 0x00 || DST_prime)
+     * b_1 = H(b_0 || 0x01 || DST_prime)
+     * b_i = H((b_0 xor b_(i-1)) || I2OSP(i, 1) || DST_prime)
+     * DST_prime = DST || I2OSP(len(DST), 1)
+     */
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut hasher = Sha256::new();
+    hasher.update([0u8; 64]);
+    for chunk in msg {
+        hasher.update(chunk);
+    }
+    hasher.update((len as u16).to_be_bytes());
+    hasher.update([0]);
+    hasher.update(&dst_prime);
+    let b_0: [u8; 32] = hasher.finalize().into();
+
+    let mut uniform = Vec::with_capacity(len);
+    let mut b_i = [0u8; 32];
+    for i in 1..=len.div_ceil(32) {
+        let mut hasher = Sha256::new();
+        b_i.iter_mut().zip(b_0).for_each(|(b, b_0)| *b ^= b_0);
+        hasher.update(b_i);
+        hasher.update([i as u8]);
+        hasher.update(&dst_prime);
+        b_i = hasher.finalize().into();
+        uniform.extend_from_slice(&b_i);
+    }
+
+    uniform.truncate(len);
+    uniform
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_message_xmd;
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd(&[b""], dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd(&[b"abc"], dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+    }
+}
@@ -0,0 +1,801 @@
+use super::{DerivationPath, HdCurve, HARDENED};
+use crate::{
+    curves::k1::K1,
+    ecmaths::{affine::ECAffinePoint, ru256::RU256},
+    error::Error,
+    hash::hash160,
+    signature::PrivateKey,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+// ******************************************************************
+// BIP-32 Hierarchical Deterministic Keys over secp256k1, and SLIP-10
+// over P-256
+// master:      I = HMAC-SHA512(seed key, seed), k = IL, c = IR
+// private:     I = HMAC-SHA512(c, 0x00 || ser256(k) || ser32(i))  (hardened, i >= 2^31)
+//              I = HMAC-SHA512(c, serP(k * G) || ser32(i))        (normal)
+//              k_i = IL + k mod n, c_i = IR
+// public:      K_i = IL * G + K (normal children only)
+// a child with IL >= n or a zero key / infinite point is invalid, and the
+// caller moves on to the next index (BIP-32), or the derivation is rerun
+// with I = HMAC-SHA512(c, 0x01 || IR || ser32(i)) (SLIP-10), an invalid
+// master key is rerun with I = HMAC-SHA512(seed key, I)
+// serialization: Base58Check(version || depth || parent fingerprint
+//                            || child number || c || 0x00 || ser256(k) / serP(K))
+//                (secp256k1 only, SLIP-10 defines no versions for P-256)
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//            https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+// ******************************************************************
+
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+#[derive(Clone)]
+pub struct ExtendedPrivateKey<C: HdCurve> {
+    key: Zeroizing<RU256>,
+    chain_code: Zeroizing<[u8; 32]>,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    curve: C,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedPublicKey<C: HdCurve> {
+    point: ECAffinePoint,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    curve: C,
+}
+
+impl<C: HdCurve> ExtendedPrivateKey<C> {
+    pub fn from_seed(seed: &[u8], curve: &C) -> Result<Self, Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut i = hmac_sha512(C::SEED_KEY, &[seed]);
+        let key = loop {
+            let key = Zeroizing::new(RU256::from_bytes(&i[..32]));
+            match check_key(&key, curve) {
+                Err(_) if C::RETRY_INVALID => i = hmac_sha512(C::SEED_KEY, &[i.as_ref()]),
+                result => break result.map(|_| key)?,
+            }
+        };
+
+        Ok(Self {
+            key,
+            chain_code: Zeroizing::new(i[32..].try_into().unwrap()),
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            curve: curve.clone(),
+        })
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        let depth = self.depth.checked_add(1).ok_or(Error::OutOfRange)?;
+        let point = self.point();
+
+        let mut i = match index >= HARDENED {
+            true => {
+                let mut key = Zeroizing::new([0u8; 33]);
+                self.key.to_bytes(&mut key[1..]);
+                hmac_sha512(
+                    self.chain_code.as_ref(),
+                    &[key.as_ref(), &index.to_be_bytes()],
+                )
+            }
+            false => hmac_sha512(
+                self.chain_code.as_ref(),
+                &[&point.to_compressed(), &index.to_be_bytes()],
+            ),
+        };
+
+        let n = &C::n();
+        let key = loop {
+            let il = Zeroizing::new(RU256::from_bytes(&i[..32]));
+            let result = match &*il < n {
+                true => {
+                    let key = Zeroizing::new(il.add_mod(&self.key, n));
+                    check_key(&key, &self.curve).map(|_| key)
+                }
+                false => Err(Error::OutOfRange),
+            };
+            match result {
+                Err(_) if C::RETRY_INVALID => i = retry(&self.chain_code, &i, index),
+                result => break result?,
+            }
+        };
+
+        Ok(Self {
+            key,
+            chain_code: Zeroizing::new(i[32..].try_into().unwrap()),
+            depth,
+            parent_fingerprint: fingerprint(&point),
+            child_number: index,
+            curve: self.curve.clone(),
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    pub fn private_key(&self) -> PrivateKey {
+        PrivateKey::new((*self.key).clone())
+    }
+
+    pub fn public_key(&self) -> ExtendedPublicKey<C> {
+        ExtendedPublicKey {
+            point: self.point(),
+            chain_code: *self.chain_code,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            curve: self.curve.clone(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.point())
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    fn point(&self) -> ECAffinePoint {
+        C::g()
+            .to_jacobian()
+            .multiply(&self.key, &self.curve)
+            .from_jacobian(&self.curve)
+    }
+}
+
+impl ExtendedPrivateKey<K1> {
+    // xprv...
+    pub fn to_base58(&self) -> String {
+        let mut key = Zeroizing::new([0u8; 33]);
+        self.key.to_bytes(&mut key[1..]);
+
+        encode(
+            &XPRV_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        )
+    }
+
+    pub fn from_base58(s: &str) -> Result<Self, Error> {
+        let data = decode(s, &XPRV_VERSION)?;
+        if data[45] != 0 {
+            return Err(Error::InvalidEncoding);
+        }
+        let key = Zeroizing::new(RU256::from_bytes(&data[46..]));
+        check_key(&key, &K1)?;
+
+        Ok(Self {
+            key,
+            chain_code: Zeroizing::new(data[13..45].try_into().unwrap()),
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: u32::from_be_bytes(data[9..13].try_into().unwrap()),
+            curve: K1,
+        })
+    }
+}
+
+impl<C: HdCurve> ExtendedPublicKey<C> {
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        if index >= HARDENED {
+            return Err(Error::HardenedDerivation);
+        }
+        let depth = self.depth.checked_add(1).ok_or(Error::OutOfRange)?;
+
+        let mut i = hmac_sha512(
+            &self.chain_code,
+            &[&self.point.to_compressed(), &index.to_be_bytes()],
+        );
+
+        let point = loop {
+            let il = RU256::from_bytes(&i[..32]);
+            let result = match il < C::n() {
+                true => {
+                    let point = C::g().to_jacobian().strauss_shamir_multiplication(
+                        &self.point.to_jacobian(),
+                        &il,
+                        &RU256::one(),
+                        &self.curve,
+                    );
+                    match point.is_zero_point() {
+                        true => Err(Error::PointAtInfinity),
+                        false => Ok(point),
+                    }
+                }
+                false => Err(Error::OutOfRange),
+            };
+            match result {
+                Err(_) if C::RETRY_INVALID => i = retry(&self.chain_code, &i, index),
+                result => break result?,
+            }
+        };
+
+        Ok(Self {
+            point: point.from_jacobian(&self.curve),
+            chain_code: i[32..].try_into().unwrap(),
+            depth,
+            parent_fingerprint: fingerprint(&self.point),
+            child_number: index,
+            curve: self.curve.clone(),
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    pub fn point(&self) -> &ECAffinePoint {
+        &self.point
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.point)
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+}
+
+impl ExtendedPublicKey<K1> {
+    // xpub...
+    pub fn to_base58(&self) -> String {
+        encode(
+            &XPUB_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.point.to_compressed(),
+        )
+    }
+
+    pub fn from_base58(s: &str) -> Result<Self, Error> {
+        let data = decode(s, &XPUB_VERSION)?;
+
+        Ok(Self {
+            point: ECAffinePoint::from_compressed(data[45..].try_into().unwrap(), &K1)?,
+            chain_code: data[13..45].try_into().unwrap(),
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: u32::from_be_bytes(data[9..13].try_into().unwrap()),
+            curve: K1,
+        })
+    }
+}
+
+fn encode(
+    version: &[u8; 4],
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_number: u32,
+    chain_code: &[u8; 32],
+    key: &[u8; 33],
+) -> String {
+    let mut data = Zeroizing::new([0u8; 78]);
+    data[..4].copy_from_slice(version);
+    data[4] = depth;
+    data[5..9].copy_from_slice(parent_fingerprint);
+    data[9..13].copy_from_slice(&child_number.to_be_bytes());
+    data[13..45].copy_from_slice(chain_code);
+    data[45..].copy_from_slice(key);
+
+    bs58::encode(data.as_ref()).with_check().into_string()
+}
+
+fn decode(s: &str, version: &[u8; 4]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let data = Zeroizing::new(
+        bs58::decode(s)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| Error::InvalidEncoding)?,
+    );
+    if data.len() != 78 || &data[..4] != version {
+        return Err(Error::InvalidEncoding);
+    }
+    // the master key has neither a parent nor an index
+    if data[4] == 0 && (data[5..9] != [0; 4] || data[9..13] != [0; 4]) {
+        return Err(Error::InvalidEncoding);
+    }
+
+    Ok(data)
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = <Hmac<Sha512> as Mac>::new_from_slice(key).unwrap();
+    for chunk in data {
+        mac.update(chunk);
+    }
+    Zeroizing::new(mac.finalize().into_bytes().into())
+}
+
+// SLIP-10 input for the next attempt after an invalid child
+fn retry(chain_code: &[u8; 32], i: &[u8; 64], index: u32) -> Zeroizing<[u8; 64]> {
+    hmac_sha512(chain_code, &[&[0x01], &i[32..], &index.to_be_bytes()])
+}
+
+// 0 < k < n
+fn check_key<C: HdCurve>(key: &RU256, curve: &C) -> Result<(), Error> {
+    PrivateKey::try_new(key.clone(), curve).map(|_| ())
+}
+
+fn fingerprint(point: &ECAffinePoint) -> [u8; 4] {
+    hash160(&point.to_compressed())[..4].try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtendedPrivateKey, ExtendedPublicKey};
+    use crate::{
+        curves::{k1::K1, r1::R1},
+        error::Error,
+        hd::{DerivationPath, HARDENED},
+    };
+    use std::str::FromStr;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vectors
+    // (path, xpub, xprv)
+    fn check_vector(seed: &str, chain: &[(&str, &str, &str)]) {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(seed).unwrap(), &K1).unwrap();
+
+        for (path, xpub, xprv) in chain {
+            let key = master
+                .derive_path(&DerivationPath::from_str(path).unwrap())
+                .unwrap();
+            assert_eq!(&key.to_base58(), xprv, "{}", path);
+            assert_eq!(&key.public_key().to_base58(), xpub, "{}", path);
+
+            let decoded = ExtendedPrivateKey::from_base58(xprv).unwrap();
+            assert_eq!(decoded.to_base58(), *xprv);
+            assert_eq!(
+                ExtendedPublicKey::from_base58(xpub).unwrap(),
+                key.public_key()
+            );
+        }
+    }
+
+    #[test]
+    fn test_vector_1() {
+        check_vector(
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                (
+                    "m",
+                    "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                    "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                ),
+                (
+                    "m/0H",
+                    "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                    "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+                ),
+                (
+                    "m/0H/1",
+                    "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                    "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+                ),
+                (
+                    "m/0H/1/2H",
+                    "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                    "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+                ),
+                (
+                    "m/0H/1/2H/2",
+                    "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+                    "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+                ),
+                (
+                    "m/0H/1/2H/2/1000000000",
+                    "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                    "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_vector_2() {
+        check_vector(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            &[
+                (
+                    "m",
+                    "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+                    "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+                ),
+                (
+                    "m/0",
+                    "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+                    "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+                ),
+                (
+                    "m/0/2147483647H",
+                    "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+                    "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+                ),
+                (
+                    "m/0/2147483647H/1",
+                    "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+                    "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+                ),
+                (
+                    "m/0/2147483647H/1/2147483646H",
+                    "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+                    "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+                ),
+                (
+                    "m/0/2147483647H/1/2147483646H/2",
+                    "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+                    "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_vector_3() {
+        // retention of leading zeros
+        check_vector(
+            "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+            &[
+                (
+                    "m",
+                    "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+                    "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+                ),
+                (
+                    "m/0H",
+                    "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+                    "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_vector_4() {
+        // retention of leading zeros for hardened derivation
+        check_vector(
+            "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+            &[
+                (
+                    "m",
+                    "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+                    "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+                ),
+                (
+                    "m/0H",
+                    "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+                    "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+                ),
+                (
+                    "m/0H/1H",
+                    "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+                    "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_public_derivation() {
+        let master = ExtendedPrivateKey::from_seed(&[0x42; 32], &K1).unwrap();
+        let account = master
+            .derive_path(&DerivationPath::from_str("m/44'/60'/0'").unwrap())
+            .unwrap();
+        let path = DerivationPath::from_str("m/0/7").unwrap();
+
+        // neutering commutes with normal derivation
+        let child = account.derive_path(&path).unwrap();
+        assert_eq!(
+            account.public_key().derive_path(&path).unwrap(),
+            child.public_key()
+        );
+        assert_eq!(
+            child.private_key().to_pub_key(&K1),
+            *child.public_key().point()
+        );
+        assert_eq!(child.depth(), 5);
+        assert_eq!(child.child_number(), 7);
+
+        assert_eq!(
+            account.public_key().derive_child(1 << 31),
+            Err(Error::HardenedDerivation)
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-5
+    // (extended key, error)
+    const INVALID_KEYS: [(&str, Error); 16] = [
+        // pubkey version / prvkey mismatch
+        ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm", Error::InvalidEncoding),
+        // prvkey version / pubkey mismatch
+        ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH", Error::InvalidEncoding),
+        // invalid pubkey prefix 04
+        ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn", Error::InvalidEncoding),
+        // invalid prvkey prefix 04
+        ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ", Error::InvalidEncoding),
+        // invalid pubkey prefix 01
+        ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4", Error::InvalidEncoding),
+        // invalid prvkey prefix 01
+        ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J", Error::InvalidEncoding),
+        // zero depth with non-zero parent fingerprint
+        ("xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv", Error::InvalidEncoding),
+        // zero depth with non-zero parent fingerprint
+        ("xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ", Error::InvalidEncoding),
+        // zero depth with non-zero index
+        ("xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN", Error::InvalidEncoding),
+        // zero depth with non-zero index
+        ("xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8", Error::InvalidEncoding),
+        // unknown extended key version
+        ("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4", Error::InvalidEncoding),
+        // unknown extended key version
+        ("DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9", Error::InvalidEncoding),
+        // private key 0 not in 1..n-1
+        ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx", Error::ZeroScalar),
+        // private key n not in 1..n-1
+        ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G", Error::OutOfRange),
+        // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+        ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY", Error::PointNotOnCurve),
+        // invalid checksum
+        ("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL", Error::InvalidEncoding),
+    ];
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            ExtendedPrivateKey::from_seed(&[0; 15], &K1).err(),
+            Some(Error::OutOfRange)
+        );
+        assert_eq!(
+            ExtendedPrivateKey::from_seed(&[0; 65], &K1).err(),
+            Some(Error::OutOfRange)
+        );
+
+        for (key, error) in INVALID_KEYS {
+            // unknown versions are rejected as both key types
+            if !key.starts_with("xpub") {
+                assert_eq!(
+                    ExtendedPrivateKey::from_base58(key).err(),
+                    Some(error.clone()),
+                    "{}",
+                    key
+                );
+            }
+            if !key.starts_with("xprv") {
+                assert_eq!(
+                    ExtendedPublicKey::from_base58(key).err(),
+                    Some(error),
+                    "{}",
+                    key
+                );
+            }
+        }
+
+        // a valid key of the other type
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        assert_eq!(
+            ExtendedPrivateKey::from_base58(xpub).err(),
+            Some(Error::InvalidEncoding)
+        );
+        assert_eq!(
+            ExtendedPublicKey::from_base58(xprv).err(),
+            Some(Error::InvalidEncoding)
+        );
+    }
+
+    // https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vectors
+    // (path, parent fingerprint, chain code, private key, public key)
+    fn check_slip10_vector(seed: &str, chain: &[[&str; 5]]) {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(seed).unwrap(), &R1).unwrap();
+
+        for [path, parent_fingerprint, chain_code, private_key, public_key] in chain {
+            let path = DerivationPath::from_str(path).unwrap();
+            let key = master.derive_path(&path).unwrap();
+            let mut key_bytes = [0u8; 32];
+            key.key.to_bytes(&mut key_bytes);
+
+            assert_eq!(hex::encode(key.parent_fingerprint()), *parent_fingerprint);
+            assert_eq!(hex::encode(key.chain_code()), *chain_code);
+            assert_eq!(hex::encode(key_bytes), *private_key);
+            assert_eq!(
+                hex::encode(key.public_key().point().to_compressed()),
+                *public_key
+            );
+
+            // public derivation of the trailing normal indices
+            let hardened = path.indices().iter().rposition(|&i| i >= HARDENED);
+            let split = hardened.map_or(0, |i| i + 1);
+            let parent = master
+                .derive_path(&DerivationPath::new(path.indices()[..split].to_vec()))
+                .unwrap();
+            let normal = DerivationPath::new(path.indices()[split..].to_vec());
+            assert_eq!(
+                parent.public_key().derive_path(&normal).unwrap(),
+                key.public_key()
+            );
+        }
+    }
+
+    #[test]
+    fn test_slip10_nist256p1_vector_1() {
+        check_slip10_vector(
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                [
+                    "m",
+                    "00000000",
+                    "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+                    "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+                    "0266874dc6ade47b3ecd096745ca09bcd29638dd52c2c12117b11ed3e458cfa9e8",
+                ],
+                [
+                    "m/0H",
+                    "be6105b5",
+                    "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+                    "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+                    "0384610f5ecffe8fda089363a41f56a5c7ffc1d81b59a612d0d649b2d22355590c",
+                ],
+                [
+                    "m/0H/1",
+                    "9b02312f",
+                    "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+                    "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+                    "03526c63f8d0b4bbbf9c80df553fe66742df4676b241dabefdef67733e070f6844",
+                ],
+                [
+                    "m/0H/1/2H",
+                    "b98005c1",
+                    "98c7514f562e64e74170cc3cf304ee1ce54d6b6da4f880f313e8204c2a185318",
+                    "694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7",
+                    "0359cf160040778a4b14c5f4d7b76e327ccc8c4a6086dd9451b7482b5a4972dda0",
+                ],
+                [
+                    "m/0H/1/2H/2",
+                    "0e9f3274",
+                    "ba96f776a5c3907d7fd48bde5620ee374d4acfd540378476019eab70790c63a0",
+                    "5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa",
+                    "029f871f4cb9e1c97f9f4de9ccd0d4a2f2a171110c61178f84430062230833ff20",
+                ],
+                [
+                    "m/0H/1/2H/2/1000000000",
+                    "8b2b5c4b",
+                    "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059",
+                    "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119",
+                    "02216cd26d31147f72427a453c443ed2cde8a1e53c9cc44e5ddf739725413fe3f4",
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_slip10_nist256p1_vector_2() {
+        check_slip10_vector(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            &[
+                [
+                    "m",
+                    "00000000",
+                    "96cd4465a9644e31528eda3592aa35eb39a9527769ce1855beafc1b81055e75d",
+                    "eaa31c2e46ca2962227cf21d73a7ef0ce8b31c756897521eb6c7b39796633357",
+                    "02c9e16154474b3ed5b38218bb0463e008f89ee03e62d22fdcc8014beab25b48fa",
+                ],
+                [
+                    "m/0",
+                    "607f628f",
+                    "84e9c258bb8557a40e0d041115b376dd55eda99c0042ce29e81ebe4efed9b86a",
+                    "d7d065f63a62624888500cdb4f88b6d59c2927fee9e6d0cdff9cad555884df6e",
+                    "039b6df4bece7b6c81e2adfeea4bcf5c8c8a6e40ea7ffa3cf6e8494c61a1fc82cc",
+                ],
+                [
+                    "m/0/2147483647H",
+                    "946d2a54",
+                    "f235b2bc5c04606ca9c30027a84f353acf4e4683edbd11f635d0dcc1cd106ea6",
+                    "96d2ec9316746a75e7793684ed01e3d51194d81a42a3276858a5b7376d4b94b9",
+                    "02f89c5deb1cae4fedc9905f98ae6cbf6cbab120d8cb85d5bd9a91a72f4c068c76",
+                ],
+                [
+                    "m/0/2147483647H/1",
+                    "218182d8",
+                    "7c0b833106235e452eba79d2bdd58d4086e663bc8cc55e9773d2b5eeda313f3b",
+                    "974f9096ea6873a915910e82b29d7c338542ccde39d2064d1cc228f371542bbc",
+                    "03abe0ad54c97c1d654c1852dfdc32d6d3e487e75fa16f0fd6304b9ceae4220c64",
+                ],
+                [
+                    "m/0/2147483647H/1/2147483646H",
+                    "931223e4",
+                    "5794e616eadaf33413aa309318a26ee0fd5163b70466de7a4512fd4b1a5c9e6a",
+                    "da29649bbfaff095cd43819eda9a7be74236539a29094cd8336b07ed8d4eff63",
+                    "03cb8cb067d248691808cd6b5a5a06b48e34ebac4d965cba33e6dc46fe13d9b933",
+                ],
+                [
+                    "m/0/2147483647H/1/2147483646H/2",
+                    "956c4629",
+                    "3bfb29ee8ac4484f09db09c2079b520ea5616df7820f071a20320366fbe226a7",
+                    "bb0a77ba01cc31d77205d51d08bd313b979a71ef4de9b062f8958297e746bd67",
+                    "020ee02e18967237cf62672983b253ee62fa4dd431f8243bfeccdf39dbe181387f",
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_slip10_retry() {
+        // IL >= n for m/28578H and the first HMAC of the seed
+        check_slip10_vector(
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                [
+                    "m/28578H",
+                    "be6105b5",
+                    "e94c8ebe30c2250a14713212f6449b20f3329105ea15b652ca5bdfc68f6c65c2",
+                    "06f0db126f023755d0b8d86d4591718a5210dd8d024e3e14b6159d63f53aa669",
+                    "02519b5554a4872e8c9c1c847115363051ec43e93400e030ba3c36b52a3e70a5b7",
+                ],
+                [
+                    "m/28578H/33941",
+                    "3e2b7bc6",
+                    "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071",
+                    "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a",
+                    "0235bfee614c0d5b2cae260000bb1d0d84b270099ad790022c1ae0b2e782efe120",
+                ],
+            ],
+        );
+        check_slip10_vector(
+            "a7305bc8df8d0951f0cb224c0e95d7707cbdf2c6ce7e8d481fec69c7ff5e9446",
+            &[[
+                "m",
+                "00000000",
+                "7762f9729fed06121fd13f326884c82f59aa95c57ac492ce8c9654e60efd130c",
+                "3b8c18469a4634517d6d0b65448f8e6c62091b45540a1743c5846be55d47d88f",
+                "0383619fadcde31063d8c5cb00dbfe1713f3e6fa169d8541a798752a1c1ca0cb20",
+            ]],
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    curves::{k1::K1, r1::R1, SECP256},
+    error::Error,
+};
+use std::{fmt, str::FromStr};
+
+pub mod bip32;
+pub mod bip39;
+
+// index of the first hardened child, written as i' or iH in paths
+pub const HARDENED: u32 = 1 << 31;
+
+// ******************************************************************
+// Curves for BIP-32 style derivation
+// SLIP-10 reuses the BIP-32 algorithm with a curve specific HMAC key for
+// the master key, and retries instead of failing when a derived key is
+// invalid, which is only reachable with a noticeable probability on P-256
+// Reference: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+// ******************************************************************
+
+pub trait HdCurve: SECP256 + Clone {
+    // HMAC-SHA512 key of the master key derivation
+    const SEED_KEY: &'static [u8];
+    // rehash an invalid key (SLIP-10) instead of returning an error (BIP-32)
+    const RETRY_INVALID: bool;
+}
+
+impl HdCurve for K1 {
+    const SEED_KEY: &'static [u8] = b"Bitcoin seed";
+    const RETRY_INVALID: bool = false;
+}
+
+impl HdCurve for R1 {
+    const SEED_KEY: &'static [u8] = b"Nist256p1 seed";
+    const RETRY_INVALID: bool = true;
+}
+
+// ******************************************************************
+// Derivation Path
+// m / purpose' / coin_type' / account' / change / address_index
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//            https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+// ******************************************************************
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    pub fn new(indices: Vec<u32>) -> Self {
+        Self(indices)
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(Error::InvalidEncoding);
+        }
+
+        components
+            .map(|component| {
+                let (index, hardened) = match component.strip_suffix(['\'', 'h', 'H']) {
+                    Some(index) => (index, true),
+                    None => (component, false),
+                };
+                // no signs, spaces or empty components
+                if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(Error::InvalidEncoding);
+                }
+
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED => Ok(index | (hardened as u32) << 31),
+                    _ => Err(Error::OutOfRange),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            match index >= &HARDENED {
+                true => write!(f, "/{}'", index - HARDENED)?,
+                false => write!(f, "/{}", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DerivationPath, HARDENED};
+    use crate::error::Error;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_derivation_path() {
+        let path = DerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+        assert_eq!(
+            path.indices(),
+            [44 + HARDENED, 60 + HARDENED, HARDENED, 0, 0]
+        );
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/0");
+        assert_eq!(DerivationPath::from_str("m/44h/60H/0'/0/0").unwrap(), path);
+
+        assert_eq!(
+            DerivationPath::from_str("m").unwrap(),
+            DerivationPath::default()
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/2147483647'").unwrap().indices(),
+            [u32::MAX]
+        );
+
+        for invalid in [
+            "", "M/0", "0/1", "m/", "m//1", "m/-1", "m/+1", "m/1''", "m/ 1", "m/a",
+        ] {
+            assert_eq!(
+                DerivationPath::from_str(invalid),
+                Err(Error::InvalidEncoding),
+                "{}",
+                invalid
+            );
+        }
+        for invalid in ["m/2147483648", "m/2147483648'", "m/99999999999"] {
+            assert_eq!(DerivationPath::from_str(invalid), Err(Error::OutOfRange));
+        }
+    }
+}
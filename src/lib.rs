@@ -1,6 +1,11 @@
 pub mod bytes;
 pub mod curves;
+pub mod ecies;
 pub mod ecmaths;
+pub mod error;
+pub mod ethereum;
+pub mod hash;
+pub mod hd;
 pub mod signature;
 
 #[cfg(test)]
@@ -25,12 +30,12 @@ mod tests {
             let signature = priv_key.raw_sign(&msg_hash, &nonce, &curve);
 
             assert!(
-                signature.raw_verify(&msg_hash, &pub_key, &curve),
+                signature.raw_verify(&msg_hash, &pub_key, &curve).is_ok(),
                 "raw verify failed in iteration {}",
                 i
             );
             assert_eq!(
-                signature.raw_recover(&msg_hash, &curve),
+                signature.raw_recover(&msg_hash, &curve).unwrap(),
                 pub_key,
                 "raw recover failed in iteration {}",
                 i
@@ -22,7 +22,7 @@ fn main() {
     println!("Signature: {:?}", signature);
 
     println!(
-        "Verify sig: {}",
+        "Verify sig: {:?}",
         signature.raw_verify(&msg_hash, &pub_key, &curve)
     );
 
@@ -0,0 +1,255 @@
+use super::{check_scalar, recovery_id::RecoveryId, Signature};
+use crate::{curves::SECP256, ecmaths::ru256::RU256, error::Error};
+
+// ******************************************************************
+// Fixed Size Signature Encodings
+// compact:  r || s                       (64 bytes, no recovery id)
+// rsv:      r || s || v                  (65 bytes, v = 27 + recovery id)
+// vrs:      v || r || s                  (65 bytes, v = 27 + recovery id)
+// EIP-2098: r || (yParity << 255 | s)    (64 bytes)
+// Reference: https://eips.ethereum.org/EIPS/eip-2098
+// ******************************************************************
+
+impl Signature {
+    pub fn to_compact(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        self.r.to_bytes(&mut bytes[..32]);
+        self.s.to_bytes(&mut bytes[32..]);
+        bytes
+    }
+
+    // r || s carries no recovery id, decoded signatures have none
+    pub fn from_compact<T: SECP256>(bytes: &[u8; 64], _: &T) -> Result<Self, Error> {
+        let n = &T::n();
+        let r = RU256::from_bytes(&bytes[..32]);
+        let s = RU256::from_bytes(&bytes[32..]);
+        check_scalar(&r, n)?;
+        check_scalar(&s, n)?;
+
+        Ok(Self {
+            r,
+            s,
+            recovery_id: None,
+        })
+    }
+
+    pub fn to_rsv(&self) -> Result<[u8; 65], Error> {
+        let mut bytes = [0; 65];
+        bytes[..64].copy_from_slice(&self.to_compact());
+        bytes[64] = self.legacy_v()?;
+        Ok(bytes)
+    }
+
+    pub fn from_rsv<T: SECP256>(bytes: &[u8; 65], curve: &T) -> Result<Self, Error> {
+        let compact = bytes[..64].try_into().unwrap();
+        Ok(Self {
+            recovery_id: Some(decode_v(bytes[64])?),
+            ..Self::from_compact(compact, curve)?
+        })
+    }
+
+    pub fn to_vrs(&self) -> Result<[u8; 65], Error> {
+        let mut bytes = [0; 65];
+        bytes[0] = self.legacy_v()?;
+        bytes[1..].copy_from_slice(&self.to_compact());
+        Ok(bytes)
+    }
+
+    pub fn from_vrs<T: SECP256>(bytes: &[u8; 65], curve: &T) -> Result<Self, Error> {
+        let compact = bytes[1..].try_into().unwrap();
+        Ok(Self {
+            recovery_id: Some(decode_v(bytes[0])?),
+            ..Self::from_compact(compact, curve)?
+        })
+    }
+
+    // only signatures with s in the lower half of n and a recovery id of 0 or 1
+    // leave the top bit of s free for yParity
+    pub fn to_eip2098<T: SECP256>(&self, curve: &T) -> Result<[u8; 64], Error> {
+        let y_parity = match self.recovery_id {
+            Some(RecoveryId {
+                y_odd,
+                x_reduced: false,
+            }) => (y_odd as u8) << 7,
+            _ => return Err(Error::InvalidV),
+        };
+        if !self.is_low_s(curve) {
+            return Err(Error::HighS);
+        }
+
+        let mut bytes = self.to_compact();
+        bytes[32] |= y_parity;
+        Ok(bytes)
+    }
+
+    pub fn from_eip2098<T: SECP256>(bytes: &[u8; 64], curve: &T) -> Result<Self, Error> {
+        let mut compact = *bytes;
+        let y_parity = compact[32] >> 7;
+        compact[32] &= 0x7f;
+
+        Ok(Self {
+            recovery_id: Some(RecoveryId::new(y_parity == 1, false)),
+            ..Self::from_compact(&compact, curve)?
+        })
+    }
+
+    fn legacy_v(&self) -> Result<u8, Error> {
+        Ok(self.recovery_id.ok_or(Error::InvalidV)?.to_legacy())
+    }
+}
+
+// accepts both the raw 0..=3 and the 27..=30 forms of the recovery id
+fn decode_v(v: u8) -> Result<RecoveryId, Error> {
+    match v {
+        0..=3 => RecoveryId::from_byte(v),
+        _ => RecoveryId::from_legacy(v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::{recovery_id::RecoveryId, PrivateKey, Signature},
+    };
+    use std::str::FromStr;
+
+    // Examples from https://eips.ethereum.org/EIPS/eip-2098
+    const EIP2098_VECTORS: [(&str, &str, bool, &str); 2] = [
+        (
+            "0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+            "0x7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            false,
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+        ),
+        (
+            "0x9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76",
+            "0x139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+            true,
+            "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+        ),
+    ];
+
+    #[test]
+    fn test_eip2098() {
+        for (r, s, y_odd, compact) in EIP2098_VECTORS {
+            let signature = Signature {
+                r: RU256::from_str(r).unwrap(),
+                s: RU256::from_str(s).unwrap(),
+                recovery_id: Some(RecoveryId::new(y_odd, false)),
+            };
+            let compact: [u8; 64] = hex::decode(compact).unwrap().try_into().unwrap();
+
+            assert_eq!(signature.to_eip2098(&K1).unwrap(), compact);
+            assert_eq!(Signature::from_eip2098(&compact, &K1).unwrap(), signature);
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &R1);
+
+        let rsv = signature.to_rsv().unwrap();
+        assert_eq!(rsv[64], signature.recovery_id.unwrap().to_legacy());
+        assert_eq!(Signature::from_rsv(&rsv, &R1).unwrap(), signature);
+
+        let vrs = signature.to_vrs().unwrap();
+        assert_eq!(vrs[1..], rsv[..64]);
+        assert_eq!(Signature::from_vrs(&vrs, &R1).unwrap(), signature);
+
+        let eip2098 = signature.normalize_s(&R1).to_eip2098(&R1).unwrap();
+        assert_eq!(
+            Signature::from_eip2098(&eip2098, &R1).unwrap(),
+            signature.normalize_s(&R1)
+        );
+
+        let compact = signature.to_compact();
+        let decoded = Signature::from_compact(&compact, &R1).unwrap();
+        assert_eq!(decoded.recovery_id, None);
+        assert!(decoded
+            .raw_verify(&msg_hash, &priv_key.to_pub_key(&R1), &R1)
+            .is_ok());
+
+        // raw recovery ids are accepted and normalized
+        let mut raw_v = rsv;
+        raw_v[64] -= 27;
+        assert_eq!(Signature::from_rsv(&raw_v, &R1).unwrap(), signature);
+    }
+
+    #[test]
+    fn test_invalid_encodings() {
+        let (r, s, _, _) = EIP2098_VECTORS[0];
+        let mut rsv = [0; 65];
+        RU256::from_str(r).unwrap().to_bytes(&mut rsv[..32]);
+        RU256::from_str(s).unwrap().to_bytes(&mut rsv[32..64]);
+
+        for v in [4, 26, 31, 0xff] {
+            rsv[64] = v;
+            assert_eq!(Signature::from_rsv(&rsv, &K1), Err(Error::InvalidV));
+        }
+        rsv[64] = 27;
+
+        let mut zero_r = rsv;
+        zero_r[..32].copy_from_slice(&[0; 32]);
+        assert_eq!(Signature::from_rsv(&zero_r, &K1), Err(Error::ZeroScalar));
+
+        let mut high_s = rsv;
+        K1::n().to_bytes(&mut high_s[32..64]);
+        assert_eq!(Signature::from_rsv(&high_s, &K1), Err(Error::OutOfRange));
+
+        let mut vrs = [0; 65];
+        vrs[0] = 29;
+        vrs[1..].copy_from_slice(&rsv[..64]);
+        assert_eq!(
+            Signature::from_vrs(&vrs, &K1).unwrap().recovery_id,
+            Some(RecoveryId::new(false, true))
+        );
+
+        let signature = Signature::from_rsv(&rsv, &K1).unwrap();
+        assert_eq!(
+            Signature {
+                recovery_id: Some(RecoveryId::new(false, true)),
+                ..signature.clone()
+            }
+            .to_eip2098(&K1),
+            Err(Error::InvalidV)
+        );
+        assert_eq!(
+            Signature {
+                s: K1::n().sub_mod(&signature.s, &K1::n()),
+                ..signature.clone()
+            }
+            .to_eip2098(&K1),
+            Err(Error::HighS)
+        );
+        // the top bit of n / 2 + 1 is clear, but it is still a high s
+        let high_s = K1::n_div_2().add_mod(&RU256::one(), &K1::n());
+        assert!(!high_s.v.bit(255));
+        assert_eq!(
+            Signature {
+                s: high_s,
+                ..signature.clone()
+            }
+            .to_eip2098(&K1),
+            Err(Error::HighS)
+        );
+        assert!(Signature {
+            s: K1::n_div_2(),
+            ..signature.clone()
+        }
+        .to_eip2098(&K1)
+        .is_ok());
+        assert_eq!(
+            Signature {
+                recovery_id: None,
+                ..signature
+            }
+            .to_rsv(),
+            Err(Error::InvalidV)
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use super::Signature;
+use crate::{ecmaths::ru256::RU256, error::Error};
+
+// ******************************************************************
+// ASN.1 DER Signature Encoding
+// 0x30 len 0x02 len(r) r 0x02 len(s) s
+// integers are big-endian, minimal and positive (0x00 prefixed if the
+// high bit is set)
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0066.mediawiki
+// ******************************************************************
+
+// DER carries no recovery id, decoded signatures have none
+impl Signature {
+    pub fn to_der(&self) -> Vec<u8> {
+        let r = encode_integer(&self.r);
+        let s = encode_integer(&self.s);
+
+        let mut der = vec![0x30, (r.len() + s.len()) as u8];
+        der.extend_from_slice(&r);
+        der.extend_from_slice(&s);
+        der
+    }
+
+    // Strict BIP-66 parsing, every deviation from the minimal encoding is rejected
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        // 0x30 len 0x02 len(r) r 0x02 len(s) s, with r and s at least 1 byte each
+        if der.len() < 8 || der.len() > 72 {
+            return Err(Error::InvalidEncoding);
+        }
+        if der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let len_r = der[3] as usize;
+        if 5 + len_r >= der.len() {
+            return Err(Error::InvalidEncoding);
+        }
+        let len_s = der[5 + len_r] as usize;
+        if len_r + len_s + 6 != der.len() {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let r = &der[4..4 + len_r];
+        let s = &der[6 + len_r..];
+        if der[2] != 0x02
+            || der[4 + len_r] != 0x02
+            || !is_strict_integer(r)
+            || !is_strict_integer(s)
+        {
+            return Err(Error::InvalidEncoding);
+        }
+
+        Ok(Self {
+            r: decode_integer(r)?,
+            s: decode_integer(s)?,
+            recovery_id: None,
+        })
+    }
+
+    // Mirrors libsecp256k1's ecdsa_signature_parse_der_lax: accepts long form
+    // lengths, padded and negative integers and trailing garbage. r or s that do
+    // not fit in 32 bytes are parsed as the (never valid) zero signature.
+    pub fn from_der_lax(der: &[u8]) -> Result<Self, Error> {
+        let mut pos = 0;
+
+        // sequence tag and length, the length itself is ignored
+        if der.get(pos) != Some(&0x30) {
+            return Err(Error::InvalidEncoding);
+        }
+        pos += 1;
+        let len = *der.get(pos).ok_or(Error::InvalidEncoding)?;
+        pos += 1;
+        if len & 0x80 != 0 {
+            let len_of_len = (len - 0x80) as usize;
+            if len_of_len > der.len() - pos {
+                return Err(Error::InvalidEncoding);
+            }
+            pos += len_of_len;
+        }
+
+        let r = lax_integer(der, &mut pos)?;
+        let s = lax_integer(der, &mut pos)?;
+
+        let zero = Self {
+            r: RU256::zero(),
+            s: RU256::zero(),
+            recovery_id: None,
+        };
+        match (decode_integer(r), decode_integer(s)) {
+            (Ok(r), Ok(s)) => Ok(Self {
+                r,
+                s,
+                recovery_id: None,
+            }),
+            _ => Ok(zero),
+        }
+    }
+}
+
+fn encode_integer(v: &RU256) -> Vec<u8> {
+    let mut bytes = [0; 33];
+    v.to_bytes(&mut bytes[1..]);
+
+    // keep one leading zero if the first significant byte has the high bit set
+    let start = bytes[1..].iter().position(|b| *b != 0).unwrap_or(31) + 1;
+    let start = match bytes[start] & 0x80 != 0 {
+        true => start - 1,
+        false => start,
+    };
+
+    let mut encoded = vec![0x02, (33 - start) as u8];
+    encoded.extend_from_slice(&bytes[start..]);
+    encoded
+}
+
+// non empty, not negative and no unnecessary leading zero
+fn is_strict_integer(bytes: &[u8]) -> bool {
+    match bytes {
+        [] => false,
+        [first, ..] if first & 0x80 != 0 => false,
+        [0x00, second, ..] if second & 0x80 == 0 => false,
+        _ => true,
+    }
+}
+
+// leading zeros are ignored, the value has to fit in 32 bytes
+fn decode_integer(bytes: &[u8]) -> Result<RU256, Error> {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    if bytes.len() - start > 32 {
+        return Err(Error::OutOfRange);
+    }
+    Ok(RU256::from_bytes(&bytes[start..]))
+}
+
+fn lax_integer<'a>(der: &'a [u8], pos: &mut usize) -> Result<&'a [u8], Error> {
+    if der.get(*pos) != Some(&0x02) {
+        return Err(Error::InvalidEncoding);
+    }
+    *pos += 1;
+
+    let mut len = *der.get(*pos).ok_or(Error::InvalidEncoding)? as usize;
+    *pos += 1;
+    if len & 0x80 != 0 {
+        let mut len_of_len = len - 0x80;
+        if len_of_len > der.len() - *pos {
+            return Err(Error::InvalidEncoding);
+        }
+        while len_of_len > 0 && der[*pos] == 0 {
+            *pos += 1;
+            len_of_len -= 1;
+        }
+        if len_of_len >= std::mem::size_of::<usize>() {
+            return Err(Error::InvalidEncoding);
+        }
+
+        len = 0;
+        for _ in 0..len_of_len {
+            len = (len << 8) + der[*pos] as usize;
+            *pos += 1;
+        }
+    }
+
+    if len > der.len() - *pos {
+        return Err(Error::InvalidEncoding);
+    }
+    let integer = &der[*pos..*pos + len];
+    *pos += len;
+    Ok(integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{k1::K1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::{PrivateKey, Signature},
+    };
+    use std::str::FromStr;
+
+    fn signature(r: &str, s: &str) -> Signature {
+        Signature {
+            r: RU256::from_str(r).unwrap(),
+            s: RU256::from_str(s).unwrap(),
+            recovery_id: None,
+        }
+    }
+
+    #[test]
+    fn test_der_round_trip() {
+        let cases = [
+            (
+                signature(
+                    "0x4e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd41",
+                    "0x181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d09",
+                ),
+                "304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d09",
+            ),
+            (
+                signature(
+                    "0x8a8f6a2a0c5b6dcd9a5f3e4c0fd08b0f1aa3c6a6bd2ba2c4f8b0c25c0e1a9b2c",
+                    "0x01",
+                ),
+                "30260221008a8f6a2a0c5b6dcd9a5f3e4c0fd08b0f1aa3c6a6bd2ba2c4f8b0c25c0e1a9b2c020101",
+            ),
+            (
+                signature("0x80", "0x00ff"),
+                "300802020080020200ff",
+            ),
+        ];
+
+        for (sig, der) in cases {
+            let der = hex::decode(der).unwrap();
+            assert_eq!(sig.to_der(), der);
+            assert_eq!(Signature::from_der(&der).unwrap(), sig);
+            assert_eq!(Signature::from_der_lax(&der).unwrap(), sig);
+        }
+    }
+
+    #[test]
+    fn test_der_signature() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &K1);
+
+        let decoded = Signature::from_der(&signature.to_der()).unwrap();
+        assert!(decoded
+            .raw_verify(&msg_hash, &priv_key.to_pub_key(&K1), &K1)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_der_strict_rejects() {
+        let cases = [
+            // too short
+            "30050201010201",
+            // wrong sequence tag
+            "310802020080020200ff",
+            // sequence length does not match
+            "300902020080020200ff",
+            // r length runs past s
+            "300802090080020200ff",
+            // wrong integer tag for r and s
+            "300803020080020200ff",
+            "300802020080030200ff",
+            // empty r
+            "30060200020200ff",
+            // negative r and s
+            "3007020180020200ff",
+            "300702020080020180",
+            // r and s with excess padding
+            "300802020001020200ff",
+            "30080202008002020001",
+            // trailing bytes
+            "300802020080020200ff00",
+        ];
+
+        for der in cases {
+            let der = hex::decode(der).unwrap();
+            assert_eq!(
+                Signature::from_der(&der),
+                Err(Error::InvalidEncoding),
+                "{}",
+                hex::encode(&der)
+            );
+        }
+
+        // 73 bytes is longer than any valid signature
+        let mut too_long = vec![0x30, 71, 0x02, 33, 0x00];
+        too_long.extend_from_slice(&[0xff; 32]);
+        too_long.extend_from_slice(&[0x02, 34, 0x00, 0x00]);
+        too_long.extend_from_slice(&[0xff; 32]);
+        assert_eq!(Signature::from_der(&too_long), Err(Error::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_der_lax() {
+        let expected = signature("0x80", "0xff");
+
+        let cases = [
+            // long form sequence length, any length is ignored
+            "30810802020080020200ff",
+            "300002020080020200ff",
+            // excess padding and negative integers
+            "30090203000080020200ff",
+            "3007020200800201ff",
+            // long form integer lengths
+            "300a02810200800282000200ff",
+            // trailing garbage
+            "300802020080020200ffdeadbeef",
+        ];
+
+        for der in cases {
+            let der = hex::decode(der).unwrap();
+            assert_eq!(
+                Signature::from_der_lax(&der),
+                Ok(expected.clone()),
+                "{}",
+                hex::encode(&der)
+            );
+        }
+
+        // integers longer than 32 bytes parse as the zero signature
+        let mut overflow = vec![0x30, 0x27, 0x02, 33, 0x01];
+        overflow.extend_from_slice(&[0xff; 32]);
+        overflow.extend_from_slice(&[0x02, 0x01, 0x01]);
+        assert_eq!(
+            Signature::from_der_lax(&overflow),
+            Ok(signature("0x00", "0x00"))
+        );
+
+        let cases = [
+            "",
+            "31",
+            "30",
+            "300803020080020200ff",
+            "300802090080020200ff",
+            "3008020200800202",
+        ];
+        for der in cases {
+            let der = hex::decode(der).unwrap();
+            assert_eq!(
+                Signature::from_der_lax(&der),
+                Err(Error::InvalidEncoding),
+                "{}",
+                hex::encode(&der)
+            );
+        }
+    }
+
+    #[test]
+    fn test_der_zero_and_max() {
+        let n_minus_1 = K1::n().sub_mod(&RU256::one(), &K1::p());
+        let sig = Signature {
+            r: RU256::zero(),
+            s: n_minus_1,
+            recovery_id: None,
+        };
+
+        let der = sig.to_der();
+        assert_eq!(&der[..5], &[0x30, 0x26, 0x02, 0x01, 0x00]);
+        assert_eq!(Signature::from_der(&der).unwrap(), sig);
+    }
+}
@@ -0,0 +1,200 @@
+use super::{check_scalar, public_key::PublicKey, PrivateKey};
+use crate::{curves::SECP256, error::Error};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+// ******************************************************************
+// Elliptic Curve Diffie-Hellman
+// Z = x(d_A * Q_B) = x(d_B * Q_A)
+// the peer point is a PublicKey, so it is on the curve and not the
+// identity, which rules out invalid curve and small subgroup points
+// Reference: https://www.secg.org/sec1-v2.pdf (3.3.1)
+//            https://www.rfc-editor.org/rfc/rfc5869 (HKDF)
+// ******************************************************************
+
+// The raw x coordinate is not uniformly random, derive_key should be used
+// to turn it into key material
+pub struct SharedSecret([u8; 32]);
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    // HKDF-SHA256 with the shared secret as input key material, okm can be
+    // at most 255 * 32 bytes
+    pub fn derive_key(&self, salt: &[u8], info: &[u8], okm: &mut [u8]) -> Result<(), Error> {
+        Hkdf::<Sha256>::new(Some(salt), &self.0)
+            .expand(info, okm)
+            .map_err(|_| Error::OutOfRange)
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl PrivateKey {
+    pub fn diffie_hellman<C: SECP256 + Clone>(
+        &self,
+        peer: &PublicKey<C>,
+    ) -> Result<SharedSecret, Error> {
+        let curve = peer.curve();
+        check_scalar(&self.0, &C::n())?;
+
+        let mut point = peer.jacobian().multiply(&self.0, curve);
+        if point.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+
+        let mut shared_secret = SharedSecret([0; 32]);
+        let mut affine = point.from_jacobian(curve);
+        affine.x.to_bytes(&mut shared_secret.0);
+        affine.x.zeroize();
+        affine.y.zeroize();
+        point.x.zeroize();
+        point.y.zeroize();
+        point.z.zeroize();
+
+        Ok(shared_secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::{affine::ECAffinePoint, ru256::RU256},
+        error::Error,
+        signature::{public_key::PublicKey, PrivateKey},
+    };
+
+    #[test]
+    fn test_cavs_p256() {
+        // NIST CAVS 14.1 KAS_ECC_CDH_PrimitiveTest.txt, [P-256] COUNT = 0..2
+        // QCAVSx, QCAVSy, dIUT, QIUTx, QIUTy, ZIUT
+        let vectors = [
+            [
+                "700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287",
+                "db71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac",
+                "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+                "ead218590119e8876b29146ff89ca61770c4edbbf97d38ce385ed281d8a6b230",
+                "28af61281fd35e2fa7002523acc85a429cb06ee6648325389f59edfce1405141",
+                "46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b",
+            ],
+            [
+                "809f04289c64348c01515eb03d5ce7ac1a8cb9498f5caa50197e58d43a86a7ae",
+                "b29d84e811197f25eba8f5194092cb6ff440e26d4421011372461f579271cda3",
+                "38f65d6dce47676044d58ce5139582d568f64bb16098d179dbab07741dd5caf5",
+                "119f2f047902782ab0c9e27a54aff5eb9b964829ca99c06b02ddba95b0a3f6d0",
+                "8f52b726664cac366fc98ac7a012b2682cbd962e5acb544671d41b9445704d1d",
+                "057d636096cb80b67a8c038c890e887d1adfa4195e9b3ce241c8a778c59cda67",
+            ],
+            [
+                "a2339c12d4a03c33546de533268b4ad667debf458b464d77443636440ee7fec3",
+                "ef48a3ab26e20220bcda2c1851076839dae88eae962869a497bf73cb66faf536",
+                "1accfaf1b97712b85a6f54b148985a1bdc4c9bec0bd258cad4b3d603f49f32c8",
+                "d9f2b79c172845bfdb560bbb01447ca5ecc0470a09513b6126902c6b4f8d1051",
+                "f815ef5ec32128d3487834764678702e64e164ff7315185e23aff5facd96d7bc",
+                "2d457b78b4614132477618a5b077965ec90730a8c81a1c75d6d4ec68005d67ec",
+            ],
+        ];
+
+        for [cavs_x, cavs_y, d_iut, iut_x, iut_y, z_iut] in vectors {
+            let peer = PublicKey::from_point(
+                ECAffinePoint::from_hex_coordinates(cavs_x, cavs_y).unwrap(),
+                &R1,
+            )
+            .unwrap();
+            let priv_key = PrivateKey::new(RU256::from_bytes(&hex::decode(d_iut).unwrap()));
+            assert_eq!(
+                priv_key.to_pub_key(&R1),
+                ECAffinePoint::from_hex_coordinates(iut_x, iut_y).unwrap()
+            );
+
+            let shared_secret = priv_key.diffie_hellman(&peer).unwrap();
+            assert_eq!(hex::encode(shared_secret.as_bytes()), z_iut);
+        }
+    }
+
+    #[test]
+    fn test_agreement() {
+        // [alice, bob, Z, HKDF(Z, "salt", "ecdh test"), HKDF(Z, "salt", "other context")]
+        fn check<T: SECP256 + Clone>(curve: &T, vector: [&str; 5]) {
+            let [alice, bob, z, okm, other_okm] = vector;
+            let alice = PrivateKey::new(RU256::from_bytes(&hex::decode(alice).unwrap()));
+            let bob = PrivateKey::new(RU256::from_bytes(&hex::decode(bob).unwrap()));
+
+            let alice_secret = alice
+                .diffie_hellman(&bob.public_key(curve).unwrap())
+                .unwrap();
+            let bob_secret = bob
+                .diffie_hellman(&alice.public_key(curve).unwrap())
+                .unwrap();
+            assert_eq!(hex::encode(alice_secret.as_bytes()), z);
+            assert_eq!(hex::encode(bob_secret.as_bytes()), z);
+
+            let mut key = [0u8; 32];
+            alice_secret
+                .derive_key(b"salt", b"ecdh test", &mut key)
+                .unwrap();
+            assert_eq!(hex::encode(key), okm);
+            bob_secret
+                .derive_key(b"salt", b"other context", &mut key)
+                .unwrap();
+            assert_eq!(hex::encode(key), other_okm);
+
+            let mut too_long = vec![0u8; 255 * 32 + 1];
+            assert_eq!(
+                alice_secret.derive_key(b"", b"", &mut too_long),
+                Err(Error::OutOfRange)
+            );
+        }
+
+        // the keys of go-ethereum crypto/ecies TestSharedKeyStatic
+        check(
+            &K1,
+            [
+                "7ebbc6a8358bc76dd73ebc557056702c8cfc34e5cfcd90eb83af0347575fd2ad",
+                "6a3d6396903245bba5837752b9e0348874e72db0c4e11e9c485a81b4ea4353b9",
+                "167ccc13ac5e8a26b131c3446030c60fbfac6aa8e31149d0869f93626a4cdf62",
+                "65ed9beacdab7ef6c4f17ef9fd521e6b118198482d79fb39b7e0c791a92d2668",
+                "8cf3e3e54b1f7ed96b634303d610aacdb43d3e2aa0b1a2c2014b5bc0b54fdcae",
+            ],
+        );
+        // dIUT of CAVS COUNT = 0 and 1
+        check(
+            &R1,
+            [
+                "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+                "38f65d6dce47676044d58ce5139582d568f64bb16098d179dbab07741dd5caf5",
+                "69854de86f85d63854b189cd4f7a556c668977ed93277edc449e9f7655b28175",
+                "f065ed26e4d5067a766acd3d668d606bc326f9eaed4f98d76c16dccd94fc3276",
+                "1ce1ce08d22f1a4b4bfa8fde6621ac5b9b2f25cd835d0370b7b4d8279ae7c546",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_invalid_peer() {
+        // G of P-256 is not a point of secp256k1, so an invalid curve point
+        // never reaches the multiplication
+        assert_eq!(
+            PublicKey::from_point(R1::g(), &K1).err(),
+            Some(Error::PointNotOnCurve)
+        );
+
+        let peer = PublicKey::from_point(K1::g(), &K1).unwrap();
+        assert_eq!(
+            PrivateKey::new(K1::n()).diffie_hellman(&peer).err(),
+            Some(Error::OutOfRange)
+        );
+        assert_eq!(
+            PrivateKey::new(RU256::zero()).diffie_hellman(&peer).err(),
+            Some(Error::ZeroScalar)
+        );
+    }
+}
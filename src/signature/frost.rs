@@ -0,0 +1,789 @@
+use super::{
+    check_scalar,
+    shamir::{lagrange_coefficient, Polynomial, Share},
+    PrivateKey,
+};
+use crate::{
+    curves::{k1::K1, r1::R1, SECP256},
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+    hash::expand_message_xmd,
+};
+use primitive_types::{U256, U512};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+// ******************************************************************
+// FROST t-of-n Threshold Schnorr Signatures
+// keygen:  a trusted dealer hands out s_i = f(i) of a random polynomial
+//          of degree t - 1 with f(0) = s, PK = s * G
+// round 1: each signer commits to D_i = d_i * G, E_i = e_i * G
+// round 2: rho_i = H1(PK || H4(m) || H5(commitment list) || i)
+//          R = Σ D_i + rho_i * E_i, c = H2(R || PK || m)
+//          z_i = d_i + e_i * rho_i + λ_i * s_i * c
+// the aggregate (R, Σ z_i) verifies as z * G == R + c * PK
+// Reference: https://www.rfc-editor.org/rfc/rfc9591
+// ******************************************************************
+
+pub trait Ciphersuite: SECP256 + Clone {
+    fn context_string() -> &'static str;
+}
+
+impl Ciphersuite for K1 {
+    fn context_string() -> &'static str {
+        "FROST-secp256k1-SHA256-v1"
+    }
+}
+
+impl Ciphersuite for R1 {
+    fn context_string() -> &'static str {
+        "FROST-P256-SHA256-v1"
+    }
+}
+
+// Splits secret_key into max_signers shares of which any min_signers can sign.
+// Returns the shares and the VSS commitment, whose first element is the group
+// public key.
+pub fn trusted_dealer_keygen<T: Ciphersuite>(
+    secret_key: &PrivateKey,
+    max_signers: u16,
+    min_signers: u16,
+    curve: &T,
+) -> Result<(Vec<Share>, Vec<ECAffinePoint>), Error> {
+    if min_signers < 2 {
+        return Err(Error::OutOfRange);
+    }
+
+    let polynomial = Polynomial::random(secret_key, min_signers, curve)?;
+    Ok((
+        polynomial.shares(max_signers, curve)?,
+        polynomial.commitment(curve),
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningCommitments {
+    pub hiding: ECAffinePoint,
+    pub binding: ECAffinePoint,
+}
+
+// consumed by frost_sign so that it can not be used twice
+pub struct SigningNonces {
+    hiding: RU256,
+    binding: RU256,
+    commitments: SigningCommitments,
+}
+
+impl SigningNonces {
+    pub fn commitments(&self) -> &SigningCommitments {
+        &self.commitments
+    }
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+// Everything the signers of one message derive from the group public key,
+// the commitment list and the message
+#[derive(Debug, Clone)]
+pub struct Session<C: Ciphersuite> {
+    group_public_key: ECAffinePoint,
+    // sorted by identifier
+    commitments: Vec<(RU256, SigningCommitments)>,
+    binding_factors: Vec<RU256>,
+    r: ECAffinePoint,
+    challenge: RU256,
+    curve: C,
+}
+
+impl<C: Ciphersuite> Session<C> {
+    pub fn new(
+        group_public_key: &ECAffinePoint,
+        commitment_list: &[(RU256, SigningCommitments)],
+        msg: &[u8],
+        curve: &C,
+    ) -> Result<Self, Error> {
+        let n = &C::n();
+        group_public_key.validate(curve)?;
+
+        let mut commitments = commitment_list.to_vec();
+        commitments.sort_by_key(|(identifier, _)| identifier.v);
+        for (i, (identifier, commitment)) in commitments.iter().enumerate() {
+            check_scalar(identifier, n)?;
+            if i > 0 && identifier == &commitments[i - 1].0 {
+                return Err(Error::InvalidEncoding);
+            }
+            commitment.hiding.validate(curve)?;
+            commitment.binding.validate(curve)?;
+        }
+
+        /*
+         * rho_i = H1(enc(PK) || H4(m) || H5(enc(commitment list)) || enc(i))
+         * enc(commitment list) = enc(i) || enc(D_i) || enc(E_i) for all i
+         */
+        let encoded_commitments: Vec<u8> = commitments
+            .iter()
+            .flat_map(|(identifier, commitment)| {
+                [
+                    &scalar_bytes(identifier)[..],
+                    &commitment.hiding.to_compressed(),
+                    &commitment.binding.to_compressed(),
+                ]
+                .concat()
+            })
+            .collect();
+        let prefix = [
+            &group_public_key.to_compressed()[..],
+            &hash::<C>("msg", msg),
+            &hash::<C>("com", &encoded_commitments),
+        ]
+        .concat();
+        let binding_factors: Vec<RU256> = commitments
+            .iter()
+            .map(|(identifier, _)| {
+                hash_to_scalar::<C>("rho", &[&prefix, &scalar_bytes(identifier)])
+            })
+            .collect();
+
+        // R = Σ D_i + rho_i * E_i
+        let (points, scalars): (Vec<_>, Vec<_>) = commitments
+            .iter()
+            .zip(&binding_factors)
+            .flat_map(|((_, commitment), rho)| {
+                [
+                    (commitment.hiding.to_jacobian(), RU256::one()),
+                    (commitment.binding.to_jacobian(), rho.clone()),
+                ]
+            })
+            .unzip();
+        let r = JacobianPoint::multi_scalar_multiplication(&points, &scalars, curve);
+        if r.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+        let r = r.from_jacobian(curve);
+
+        Ok(Self {
+            challenge: challenge::<C>(&r, group_public_key, msg),
+            group_public_key: group_public_key.clone(),
+            commitments,
+            binding_factors,
+            r,
+            curve: curve.clone(),
+        })
+    }
+
+    pub fn verify_signature_share(
+        &self,
+        identifier: &RU256,
+        verifying_share: &ECAffinePoint,
+        signature_share: &RU256,
+    ) -> Result<(), Error> {
+        /*
+         * z_i * G - (c * λ_i) * PK_i == D_i + rho_i * E_i
+         */
+        let n = &C::n();
+        if signature_share >= n {
+            return Err(Error::OutOfRange);
+        }
+        verifying_share.validate(&self.curve)?;
+
+        let index = self.index(identifier)?;
+        let commitment = &self.commitments[index].1;
+        let commitment_share = commitment
+            .hiding
+            .to_jacobian()
+            .strauss_shamir_multiplication(
+                &commitment.binding.to_jacobian(),
+                &RU256::one(),
+                &self.binding_factors[index],
+                &self.curve,
+            )
+            .from_jacobian(&self.curve);
+
+        let c = self.challenge.mul_mod(&self.lambda(index)?, n);
+        let l = C::g()
+            .to_jacobian()
+            .strauss_shamir_multiplication(
+                &verifying_share.to_jacobian(),
+                signature_share,
+                &n.sub_mod(&c, n),
+                &self.curve,
+            )
+            .from_jacobian(&self.curve);
+
+        match l == commitment_share {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+
+    pub fn aggregate(&self, signature_shares: &[RU256]) -> Result<FrostSignature, Error> {
+        let n = &C::n();
+        let mut z = RU256::zero();
+        for signature_share in signature_shares {
+            if signature_share >= n {
+                return Err(Error::OutOfRange);
+            }
+            z = z.add_mod(signature_share, n);
+        }
+
+        Ok(FrostSignature {
+            r: self.r.clone(),
+            z,
+        })
+    }
+
+    pub fn group_public_key(&self) -> &ECAffinePoint {
+        &self.group_public_key
+    }
+
+    fn index(&self, identifier: &RU256) -> Result<usize, Error> {
+        self.commitments
+            .iter()
+            .position(|(i, _)| i == identifier)
+            .ok_or(Error::UnknownKey)
+    }
+
+    // Lagrange coefficient of the signer at index for x = 0
+    fn lambda(&self, index: usize) -> Result<RU256, Error> {
+        let identifiers: Vec<RU256> = self
+            .commitments
+            .iter()
+            .map(|(identifier, _)| identifier.clone())
+            .collect();
+
+        lagrange_coefficient(
+            &identifiers,
+            &self.commitments[index].0,
+            &RU256::zero(),
+            &self.curve,
+        )
+    }
+}
+
+impl PrivateKey {
+    // Round 1, with fresh randomness from the OS
+    pub fn frost_commit<T: Ciphersuite>(
+        &self,
+        curve: &T,
+    ) -> Result<(SigningNonces, SigningCommitments), Error> {
+        let mut randomness = [0u8; 64];
+        getrandom::getrandom(&mut randomness).map_err(|_| Error::RandomSource)?;
+
+        let result = self.frost_commit_with_randomness(
+            randomness[..32].try_into().unwrap(),
+            randomness[32..].try_into().unwrap(),
+            curve,
+        );
+        randomness.zeroize();
+        result
+    }
+
+    pub fn frost_commit_with_randomness<T: Ciphersuite>(
+        &self,
+        hiding_randomness: &[u8; 32],
+        binding_randomness: &[u8; 32],
+        curve: &T,
+    ) -> Result<(SigningNonces, SigningCommitments), Error> {
+        /*
+         * nonce = H3(random_bytes || enc(s_i))
+         */
+        check_scalar(&self.0, &T::n())?;
+        let mut secret = scalar_bytes(&self.0);
+        let hiding = hash_to_scalar::<T>("nonce", &[hiding_randomness, &secret]);
+        let binding = hash_to_scalar::<T>("nonce", &[binding_randomness, &secret]);
+        secret.zeroize();
+
+        let commit = |k: &RU256| T::g().to_jacobian().multiply(k, curve).from_jacobian(curve);
+        let commitments = SigningCommitments {
+            hiding: commit(&hiding),
+            binding: commit(&binding),
+        };
+
+        Ok((
+            SigningNonces {
+                hiding,
+                binding,
+                commitments: commitments.clone(),
+            },
+            commitments,
+        ))
+    }
+
+    pub fn frost_sign<C: Ciphersuite>(
+        &self,
+        identifier: &RU256,
+        nonces: SigningNonces,
+        session: &Session<C>,
+    ) -> Result<RU256, Error> {
+        /*
+         * z_i = d_i + e_i * rho_i + λ_i * s_i * c
+         */
+        let n = &C::n();
+        check_scalar(&self.0, n)?;
+
+        let index = session.index(identifier)?;
+        if session.commitments[index].1 != nonces.commitments {
+            return Err(Error::UnknownKey);
+        }
+
+        let mut key_share = session
+            .lambda(index)?
+            .mul_mod(&self.0, n)
+            .mul_mod(&session.challenge, n);
+        let z = nonces
+            .hiding
+            .add_mod(
+                &nonces.binding.mul_mod(&session.binding_factors[index], n),
+                n,
+            )
+            .add_mod(&key_share, n);
+        key_share.zeroize();
+
+        Ok(z)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrostSignature {
+    pub r: ECAffinePoint,
+    pub z: RU256,
+}
+
+impl FrostSignature {
+    // enc(R) || enc(z)
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0; 65];
+        bytes[..33].copy_from_slice(&self.r.to_compressed());
+        self.z.to_bytes(&mut bytes[33..]);
+        bytes
+    }
+
+    pub fn from_bytes<T: Ciphersuite>(bytes: &[u8; 65], curve: &T) -> Result<Self, Error> {
+        let z = RU256::from_bytes(&bytes[33..]);
+        if z >= T::n() {
+            return Err(Error::OutOfRange);
+        }
+
+        Ok(Self {
+            r: ECAffinePoint::from_compressed(bytes[..33].try_into().unwrap(), curve)?,
+            z,
+        })
+    }
+
+    pub fn verify<T: Ciphersuite>(
+        &self,
+        msg: &[u8],
+        group_public_key: &ECAffinePoint,
+        curve: &T,
+    ) -> Result<(), Error> {
+        /*
+         * z * G - c * PK == R
+         */
+        let n = &T::n();
+        if &self.z >= n {
+            return Err(Error::OutOfRange);
+        }
+        self.r.validate(curve)?;
+        group_public_key.validate(curve)?;
+
+        let c = challenge::<T>(&self.r, group_public_key, msg);
+        let l = T::g()
+            .to_jacobian()
+            .strauss_shamir_multiplication(
+                &group_public_key.to_jacobian(),
+                &self.z,
+                &n.sub_mod(&c, n),
+                curve,
+            )
+            .from_jacobian(curve);
+
+        match l == self.r {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+}
+
+fn challenge<T: Ciphersuite>(
+    r: &ECAffinePoint,
+    group_public_key: &ECAffinePoint,
+    msg: &[u8],
+) -> RU256 {
+    hash_to_scalar::<T>(
+        "chal",
+        &[&r.to_compressed(), &group_public_key.to_compressed(), msg],
+    )
+}
+
+// H1, H2, H3: hash_to_field with DST = context_string || tag and L = 48
+fn hash_to_scalar<T: Ciphersuite>(tag: &str, data: &[&[u8]]) -> RU256 {
+    let dst = [T::context_string().as_bytes(), tag.as_bytes()].concat();
+    let uniform = expand_message_xmd(data, &dst, 48);
+
+    let reduced = U512::from_big_endian(&uniform) % U512::from(T::n().v);
+    RU256 {
+        v: U256::try_from(reduced).unwrap(),
+    }
+}
+
+// H4, H5: sha256(context_string || tag || data)
+fn hash<T: Ciphersuite>(tag: &str, data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(T::context_string());
+    hasher.update(tag);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn scalar_bytes(scalar: &RU256) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    scalar.to_bytes(&mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{trusted_dealer_keygen, Ciphersuite, FrostSignature, Session, SigningCommitments};
+    use crate::{
+        curves::{k1::K1, r1::R1},
+        ecmaths::{affine::ECAffinePoint, ru256::RU256},
+        error::Error,
+        signature::{
+            shamir::{verify_share, Polynomial, Share},
+            PrivateKey,
+        },
+    };
+
+    fn scalar(hex: &str) -> RU256 {
+        RU256::from_bytes(&hex::decode(hex).unwrap())
+    }
+
+    // RFC 9591 E.5 (secp256k1) and E.4 (P-256), 2-of-3 signed by 1 and 3
+    struct Vector {
+        group_secret_key: &'static str,
+        group_public_key: &'static str,
+        coefficient: &'static str,
+        shares: [&'static str; 3],
+        participants: [Participant; 2],
+        signature: &'static str,
+    }
+
+    struct Participant {
+        identifier: u16,
+        // hiding and binding nonce randomness and the resulting nonces, None
+        // when only the commitments are used
+        nonces: Option<([&'static str; 2], [&'static str; 2])>,
+        commitments: [&'static str; 2],
+        binding_factor: &'static str,
+        signature_share: &'static str,
+    }
+
+    const K1_VECTOR: Vector = Vector {
+        group_secret_key: "0d004150d27c3bf2a42f312683d35fac7394b1e9e318249c1bfe7f0795a83114",
+        group_public_key: "02f37c34b66ced1fb51c34a90bdae006901f10625cc06c4f64663b0eae87d87b4f",
+        coefficient: "fbf85eadae3058ea14f19148bb72b45e4399c0b16028acaf0395c9b03c823579",
+        shares: [
+            "08f89ffe80ac94dcb920c26f3f46140bfc7f95b493f8310f5fc1ea2b01f4254c",
+            "04f0feac2edcedc6ce1253b7fab8c86b856a797f44d83d82a385554e6e401984",
+            "00e95d59dd0d46b0e303e500b62b7ccb0e555d49f5b849f5e748c071da8c0dbc",
+        ],
+        participants: [
+            Participant {
+                identifier: 1,
+                nonces: Some((
+                    [
+                        "7ea5ed09af19f6ff21040c07ec2d2adbd35b759da5a401d4c99dd26b82391cb2",
+                        "47acab018f116020c10cb9b9abdc7ac10aae1b48ca6e36dc15acb6ec9be5cdc5",
+                    ],
+                    [
+                        "841d3a6450d7580b4da83c8e618414d0f024391f2aeb511d7579224420aa81f0",
+                        "8d2624f532af631377f33cf44b5ac5f849067cae2eacb88680a31e77c79b5a80",
+                    ],
+                )),
+                commitments: [
+                    "03c699af97d26bb4d3f05232ec5e1938c12f1e6ae97643c8f8f11c9820303f1904",
+                    "02fa2aaccd51b948c9dc1a325d77226e98a5a3fe65fe9ba213761a60123040a45e",
+                ],
+                binding_factor: "3e08fe561e075c653cbfd46908a10e7637c70c74f0a77d5fd45d1a750c739ec6",
+                signature_share: "c4fce1775a1e141fb579944166eab0d65eefe7b98d480a569bbbfcb14f91c197",
+            },
+            Participant {
+                identifier: 3,
+                nonces: None,
+                commitments: [
+                    "03077507ba327fc074d2793955ef3410ee3f03b82b4cdc2370f71d865beb926ef6",
+                    "02ad53031ddfbbacfc5fbda3d3b0c2445c8e3e99cbc4ca2db2aa283fa68525b135",
+                ],
+                binding_factor: "93f79041bb3fd266105be251adaeb5fd7f8b104fb554a4ba9a0becea48ddbfd7",
+                signature_share: "0160fd0d388932f4826d2ebcd6b9eaba734f7c71cf25b4279a4ca2581e47b18d",
+            },
+        ],
+        signature: "0205b6d04d3774c8929413e3c76024d54149c372d57aae62574ed74319b5ea14d0c65dde8492a7471437e6c2fe3da49b90d23f642b5c6dbe7e36089f096dd97324",
+    };
+
+    const R1_VECTOR: Vector = Vector {
+        group_secret_key: "8ba9bba2e0fd8c4767154d35a0b7562244a4aaf6f36c8fb8735fa48b301bd8de",
+        group_public_key: "023a309ad94e9fe8a7ba45dfc58f38bf091959d3c99cfbd02b4dc00585ec45ab70",
+        coefficient: "80f25e6c0709353e46bfbe882a11bdbb1f8097e46340eb8673b7e14556e6c3a4",
+        shares: [
+            "0c9c1a0fe806c184add50bbdcac913dda73e482daf95dcb9f35dbb0d8a9f7731",
+            "8d8e787bef0ff6c2f494ca45f4dad198c6bee01212d6c84067159c52e1863ad5",
+            "0e80d6e8f6192c003b5488ce1eec8f5429587d48cf001541e713b2d53c09d928",
+        ],
+        participants: [
+            Participant {
+                identifier: 1,
+                nonces: Some((
+                    [
+                        "ec4c891c85fee802a9d757a67d1252e7f4e5efb8a538991ac18fbd0e06fb6fd3",
+                        "9334e29d09061223f69a09421715a347e4e6deba77444c8f42b0c833f80f4ef9",
+                    ],
+                    [
+                        "9f0542a5ba879a58f255c09f06da7102ef6a2dec6279700c656d58394d8facd4",
+                        "6513dfe7429aa2fc972c69bb495b27118c45bbc6e654bb9dc9be55385b55c0d7",
+                    ],
+                )),
+                commitments: [
+                    "0213b3e6298bf8ad46fd5e9389519a8665d63d98f4ec6a1fcca434e809d2d8070e",
+                    "02188ff1390bf69374d7b272e454b1878ef10a6b6ea3ff36f114b300b4dbd5233b",
+                ],
+                binding_factor: "7925f0d4693f204e6e59233e92227c7124664a99739d2c06b81cf64ddf90559e",
+                signature_share: "400308eaed7a2ddee02a265abe6a1cfe04d946ee8720768899619cfabe7a3aeb",
+            },
+            Participant {
+                identifier: 3,
+                nonces: Some((
+                    [
+                        "c0451c5a0a5480d6c1f860e5db7d655233dca2669fd90ff048454b8ce983367b",
+                        "2ba5f7793ae700e40e78937a82f407dd35e847e33d1e607b5c7eb6ed2a8ed799",
+                    ],
+                    [
+                        "f73444a8972bcda9e506bbca3d2b1c083c10facdf4bb5d47fef7c2dc1d9f2a0d",
+                        "44c6a29075d6e7e4f8b97796205f9e22062e7835141470afe9417fd317c1c303",
+                    ],
+                )),
+                commitments: [
+                    "033ac9a5fe4a8b57316ba1c34e8a6de453033b750e8984924a984eb67a11e73a3f",
+                    "03a7a2480ee16199262e648aea3acab628a53e9b8c1945078f2ddfbdc98b7df369",
+                ],
+                binding_factor: "e10d24a8a403723bcb6f9bb4c537f316593683b472f7a89f166630dde11822c4",
+                signature_share: "561da3c179edbb0502d941bb3e3ace3c37d122aaa46fb54499f15f3a3331de44",
+            },
+        ],
+        signature: "026d8d434874f87bdb7bc0dfd239b2c00639044f9dcb195e9a04426f70bfa4b70d9620acac6767e8e3e3036815fca4eb3a3caa69992b902bcd3352fc34f1ac192f",
+    };
+
+    fn shares<T: Ciphersuite>(vector: &Vector, curve: &T) -> (Vec<Share>, Vec<ECAffinePoint>) {
+        let polynomial = Polynomial::from_coefficients(
+            vec![scalar(vector.group_secret_key), scalar(vector.coefficient)],
+            curve,
+        )
+        .unwrap();
+        (
+            polynomial.shares(3, curve).unwrap(),
+            polynomial.commitment(curve),
+        )
+    }
+
+    fn point<T: Ciphersuite>(hex: &str, curve: &T) -> ECAffinePoint {
+        ECAffinePoint::from_compressed(&hex::decode(hex).unwrap().try_into().unwrap(), curve)
+            .unwrap()
+    }
+
+    fn check_keygen<T: Ciphersuite>(vector: &Vector, curve: &T) {
+        let (shares, vss_commitment) = shares(vector, curve);
+
+        assert_eq!(
+            hex::encode(vss_commitment[0].to_compressed()),
+            vector.group_public_key
+        );
+        for (share, expected) in shares.iter().zip(vector.shares) {
+            assert_eq!(share.value.0, scalar(expected));
+            assert!(verify_share(share, &vss_commitment, curve).is_ok());
+        }
+    }
+
+    fn check_signing<T: Ciphersuite>(vector: &Vector, curve: &T) {
+        let (shares, vss_commitment) = shares(vector, curve);
+        let group_public_key = &vss_commitment[0];
+        let msg = b"test";
+
+        // round one
+        let mut signer_nonces = vec![];
+        let mut commitment_list = vec![];
+        for participant in &vector.participants {
+            let share = &shares[participant.identifier as usize - 1];
+            let commitments = SigningCommitments {
+                hiding: point(participant.commitments[0], curve),
+                binding: point(participant.commitments[1], curve),
+            };
+
+            if let Some((randomness, expected)) = participant.nonces {
+                let (nonces, derived) = share
+                    .value
+                    .frost_commit_with_randomness(
+                        &hex::decode(randomness[0]).unwrap().try_into().unwrap(),
+                        &hex::decode(randomness[1]).unwrap().try_into().unwrap(),
+                        curve,
+                    )
+                    .unwrap();
+                assert_eq!(nonces.hiding, scalar(expected[0]));
+                assert_eq!(nonces.binding, scalar(expected[1]));
+                assert_eq!(derived, commitments);
+                signer_nonces.push(Some(nonces));
+            } else {
+                signer_nonces.push(None);
+            }
+            commitment_list.push((share.identifier.clone(), commitments));
+        }
+
+        // round two
+        let session = Session::new(group_public_key, &commitment_list, msg, curve).unwrap();
+        assert_eq!(
+            hex::encode(session.r.to_compressed()),
+            vector.signature[..66]
+        );
+
+        let mut signature_shares = vec![];
+        for (participant, nonces) in vector.participants.iter().zip(signer_nonces) {
+            let share = &shares[participant.identifier as usize - 1];
+            let index = session.index(&share.identifier).unwrap();
+            assert_eq!(
+                session.binding_factors[index],
+                scalar(participant.binding_factor)
+            );
+
+            let signature_share = scalar(participant.signature_share);
+            if let Some(nonces) = nonces {
+                assert_eq!(
+                    share
+                        .value
+                        .frost_sign(&share.identifier, nonces, &session)
+                        .unwrap(),
+                    signature_share
+                );
+            }
+            assert!(session
+                .verify_signature_share(
+                    &share.identifier,
+                    &share.value.to_pub_key(curve),
+                    &signature_share
+                )
+                .is_ok());
+            signature_shares.push(signature_share);
+        }
+
+        let signature = session.aggregate(&signature_shares).unwrap();
+        assert_eq!(hex::encode(signature.to_bytes()), vector.signature);
+        assert!(signature.verify(msg, group_public_key, curve).is_ok());
+    }
+
+    #[test]
+    fn test_keygen_vectors() {
+        check_keygen(&K1_VECTOR, &K1);
+        check_keygen(&R1_VECTOR, &R1);
+    }
+
+    #[test]
+    fn test_signing_vectors() {
+        check_signing(&K1_VECTOR, &K1);
+        check_signing(&R1_VECTOR, &R1);
+    }
+
+    #[test]
+    fn test_sign() {
+        fn check<T: Ciphersuite>(curve: &T) {
+            let secret_key = PrivateKey::random(curve).unwrap();
+            let (shares, vss_commitment) = trusted_dealer_keygen(&secret_key, 3, 2, curve).unwrap();
+            let group_public_key = &vss_commitment[0];
+            assert_eq!(group_public_key, &secret_key.to_pub_key(curve));
+            let msg = b"test";
+
+            // participants 1 and 3
+            let signers = [&shares[0], &shares[2]];
+            let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+                .iter()
+                .map(|share| {
+                    let (nonces, commitments) = share.value.frost_commit(curve).unwrap();
+                    (nonces, (share.identifier.clone(), commitments))
+                })
+                .unzip();
+            let session = Session::new(group_public_key, &commitments, msg, curve).unwrap();
+
+            let signature_shares: Vec<RU256> = signers
+                .iter()
+                .zip(nonces)
+                .map(|(share, nonces)| {
+                    share
+                        .value
+                        .frost_sign(&share.identifier, nonces, &session)
+                        .unwrap()
+                })
+                .collect();
+            for (share, signature_share) in signers.iter().zip(&signature_shares) {
+                let verifying_share = share.value.to_pub_key(curve);
+                assert!(session
+                    .verify_signature_share(&share.identifier, &verifying_share, signature_share)
+                    .is_ok());
+                assert_eq!(
+                    session.verify_signature_share(
+                        &shares[1].identifier,
+                        &verifying_share,
+                        signature_share
+                    ),
+                    Err(Error::UnknownKey)
+                );
+            }
+            assert_eq!(
+                session.verify_signature_share(
+                    &signers[0].identifier,
+                    &signers[1].value.to_pub_key(curve),
+                    &signature_shares[0]
+                ),
+                Err(Error::InvalidSignature)
+            );
+
+            let signature = session.aggregate(&signature_shares).unwrap();
+            assert!(signature.verify(msg, group_public_key, curve).is_ok());
+            let decoded = FrostSignature::from_bytes(&signature.to_bytes(), curve).unwrap();
+            assert_eq!(decoded, signature);
+
+            assert_eq!(
+                signature.verify(b"tesu", group_public_key, curve),
+                Err(Error::InvalidSignature)
+            );
+            let partial = session.aggregate(&signature_shares[..1]).unwrap();
+            assert_eq!(
+                partial.verify(msg, group_public_key, curve),
+                Err(Error::InvalidSignature)
+            );
+
+            // a reused or foreign nonce is rejected
+            let (nonces, _) = signers[0].value.frost_commit(curve).unwrap();
+            assert_eq!(
+                signers[0]
+                    .value
+                    .frost_sign(&signers[0].identifier, nonces, &session),
+                Err(Error::UnknownKey)
+            );
+
+            // duplicate identifiers
+            let duplicate = [commitments[0].clone(), commitments[0].clone()];
+            assert_eq!(
+                Session::new(group_public_key, &duplicate, msg, curve).err(),
+                Some(Error::InvalidEncoding)
+            );
+            assert_eq!(
+                trusted_dealer_keygen(&secret_key, 3, 4, curve).err(),
+                Some(Error::OutOfRange)
+            );
+        }
+        check(&K1);
+        check(&R1);
+    }
+}
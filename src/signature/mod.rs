@@ -0,0 +1,724 @@
+use crate::{
+    curves::SECP256,
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+};
+use public_key::PublicKey;
+use recovery_id::RecoveryId;
+use zeroize::{Zeroize, Zeroizing};
+
+pub mod compact;
+pub mod der;
+pub mod ecdh;
+pub mod frost;
+pub mod musig2;
+pub mod public_key;
+pub mod recovery_id;
+pub mod schnorr;
+pub mod shamir;
+pub mod tweak;
+
+// the secret is wiped when the key is dropped
+pub struct PrivateKey(RU256);
+impl PrivateKey {
+    // unchecked, see `try_new`
+    pub fn new(key: RU256) -> Self {
+        Self(key)
+    }
+
+    // d has to be in [1, n - 1]
+    pub fn try_new<T: SECP256>(key: RU256, _: &T) -> Result<Self, Error> {
+        check_scalar(&key, &T::n())?;
+        Ok(Self(key))
+    }
+
+    pub fn random<T: SECP256>(curve: &T) -> Result<Self, Error> {
+        Ok(Self((*random_scalar(curve)?).clone()))
+    }
+
+    pub fn to_pub_key<T: SECP256>(&self, curve: &T) -> ECAffinePoint {
+        T::g()
+            .to_jacobian()
+            .multiply(&self.0, curve)
+            .from_jacobian(curve)
+    }
+
+    pub fn public_key<T: SECP256 + Clone>(&self, curve: &T) -> Result<PublicKey<T>, Error> {
+        PublicKey::from_point(self.to_pub_key(curve), curve)
+    }
+
+    pub fn raw_sign<T: SECP256>(&self, msg_hash: &RU256, nonce: &RU256, curve: &T) -> Signature {
+        /*
+         * k = nonce
+         * R = k * G
+         * r = R.x % n
+         * s = 1/k * (h + (d * r))
+         * recovery id = (R.y % 2 == 1, R.x >= n)
+         */
+        let n = &T::n();
+
+        let encoded_nonce = T::g()
+            .to_jacobian()
+            .multiply(nonce, curve)
+            .from_jacobian(curve);
+        let r = RU256 {
+            v: encoded_nonce.x.v % n.v,
+        };
+        let s = msg_hash
+            .add_mod(&r.mul_mod(&self.0, n), n)
+            .div_mod(nonce, n);
+        let recovery_id = RecoveryId::new(encoded_nonce.y.v.bit(0), &encoded_nonce.x >= n);
+
+        // use lower order of n, flipping s also flips the parity of R.y
+        Signature {
+            r,
+            s,
+            recovery_id: Some(recovery_id),
+        }
+        .normalize_s(curve)
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub r: RU256,
+    pub s: RU256,
+    // `None` for encodings that do not carry one, such as DER
+    pub recovery_id: Option<RecoveryId>,
+}
+
+impl Signature {
+    pub fn raw_verify<T: SECP256>(
+        &self,
+        msg_hash: &RU256,
+        pub_key: &ECAffinePoint,
+        curve: &T,
+    ) -> Result<(), Error> {
+        /*
+         * sInv = 1/s
+         * a = G * (sInv * h)
+         * b = PubKey * (sInv * r)
+         * c = a + b
+         * c.x % n == r
+         */
+
+        let n = &T::n();
+
+        pub_key.validate(curve)?;
+        check_scalar(&self.r, n)?;
+        check_scalar(&self.s, n)?;
+
+        let s_inv = RU256::one().div_mod(&self.s, n);
+        let a = msg_hash.mul_mod(&s_inv, n);
+        let b = self.r.mul_mod(&s_inv, n);
+        let c = T::g().to_jacobian().strauss_shamir_multiplication(
+            &pub_key.to_jacobian(),
+            &a,
+            &b,
+            curve,
+        );
+
+        check_verification_point(&c, &self.r, curve)
+    }
+
+    // Consensus style verification (BIP-146), s also has to be in the lower half of n
+    pub fn raw_verify_strict<T: SECP256>(
+        &self,
+        msg_hash: &RU256,
+        pub_key: &ECAffinePoint,
+        curve: &T,
+    ) -> Result<(), Error> {
+        if !self.is_low_s(curve) {
+            return Err(Error::HighS);
+        }
+
+        self.raw_verify(msg_hash, pub_key, curve)
+    }
+
+    // Returns the indices of the signatures that fail, empty if the whole batch is valid
+    pub fn raw_verify_batch<T: SECP256>(
+        batch: &[(&RU256, &ECAffinePoint, &Signature)],
+        curve: &T,
+    ) -> Result<Vec<usize>, Error> {
+        /*
+         * the recovery id pins down R_i, and s * R = h * G + r * PubKey for a valid signature
+         * a_0 = 1, a_i random in [1, n - 1]
+         *
+         * Σ a_i * (s_i * R_i - h_i * G - r_i * PubKey_i) == 0
+         * checked as a single multi scalar multiplication
+         * -(Σ a_i * h_i) * G + Σ (a_i * s_i) * R_i + Σ (-a_i * r_i) * PubKey_i == 0
+         *
+         * signatures without a usable recovery id can only be verified one by one
+         */
+        let n = &T::n();
+        let mut points = vec![T::g().to_jacobian()];
+        let mut scalars = vec![RU256::zero()];
+        let mut g_scalar = RU256::zero();
+
+        for (i, (msg_hash, pub_key, signature)) in batch.iter().enumerate() {
+            let nonce_point = match (
+                pub_key.validate(curve),
+                check_scalar(&signature.r, n),
+                check_scalar(&signature.s, n),
+                signature.recovery_id,
+            ) {
+                (Ok(()), Ok(()), Ok(()), Some(recovery_id)) => {
+                    match signature.nonce_point(recovery_id, curve) {
+                        Ok(nonce_point) => nonce_point,
+                        Err(_) => return Ok(Self::failures(batch, curve)),
+                    }
+                }
+                _ => return Ok(Self::failures(batch, curve)),
+            };
+
+            let a = match i {
+                0 => RU256::one(),
+                _ => (*random_scalar(curve)?).clone(),
+            };
+
+            g_scalar = g_scalar.add_mod(&a.mul_mod(msg_hash, n), n);
+            points.push(nonce_point.to_jacobian());
+            scalars.push(a.mul_mod(&signature.s, n));
+            points.push(pub_key.to_jacobian());
+            scalars.push(n.sub_mod(&a.mul_mod(&signature.r, n), n));
+        }
+        scalars[0] = n.sub_mod(&g_scalar, n);
+
+        match JacobianPoint::multi_scalar_multiplication(&points, &scalars, curve).is_zero_point() {
+            true => Ok(vec![]),
+            false => Ok(Self::failures(batch, curve)),
+        }
+    }
+
+    // the batch equation only says that some signature is invalid, or that a
+    // recovery id is wrong, raw_verify decides which ones actually fail
+    fn failures<T: SECP256>(
+        batch: &[(&RU256, &ECAffinePoint, &Signature)],
+        curve: &T,
+    ) -> Vec<usize> {
+        batch
+            .iter()
+            .enumerate()
+            .filter(|(_, (msg_hash, pub_key, signature))| {
+                signature.raw_verify(msg_hash, pub_key, curve).is_err()
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn raw_recover<T: SECP256>(
+        self,
+        msg_hash: &RU256,
+        curve: &T,
+    ) -> Result<ECAffinePoint, Error> {
+        /*
+         * check that x is a valid point on curve
+         *
+         * PubKey = ((encoded_nonce * s) + (G * (-h))) / r
+         */
+
+        let recovery_id = self.recovery_id.ok_or(Error::InvalidV)?;
+
+        let n = &T::n();
+        check_scalar(&self.r, n)?;
+        check_scalar(&self.s, n)?;
+
+        // prove that x is a valid x on elliptic curve y**2 = x**3 + ax + b
+        let encoded_nonce = self.nonce_point(recovery_id, curve)?;
+
+        let c = encoded_nonce.to_jacobian().strauss_shamir_multiplication(
+            &T::g().to_jacobian(),
+            &self.s,
+            &n.sub_mod(msg_hash, n),
+            curve,
+        );
+
+        let pub_key = c
+            .multiply(&RU256::one().div_mod(&self.r, n), curve)
+            .from_jacobian(curve);
+        if pub_key.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+
+        Ok(pub_key)
+    }
+
+    // Every (recovery id, PubKey) that the signature is valid for when the recovery id is unknown
+    pub fn raw_recover_candidates<T: SECP256>(
+        &self,
+        msg_hash: &RU256,
+        curve: &T,
+    ) -> Vec<(RecoveryId, ECAffinePoint)> {
+        (0..4)
+            .filter_map(|byte| {
+                let recovery_id = RecoveryId::from_byte(byte).ok()?;
+                let candidate = Signature {
+                    recovery_id: Some(recovery_id),
+                    ..self.clone()
+                };
+                let pub_key = candidate.raw_recover(msg_hash, curve).ok()?;
+
+                Some((recovery_id, pub_key))
+            })
+            .collect()
+    }
+
+    pub fn is_low_s<T: SECP256>(&self, _: &T) -> bool {
+        self.s <= T::n_div_2()
+    }
+
+    // The low s form of the signature, a no-op for signatures already in the lower half
+    pub fn normalize_s<T: SECP256>(&self, curve: &T) -> Signature {
+        match self.is_low_s(curve) {
+            true => self.clone(),
+            false => self.malleate(curve),
+        }
+    }
+
+    /*
+     * (r, s) and (r, n - s) are both valid for the same message and key,
+     * -s corresponds to the nonce point -R which flips the parity of y
+     */
+    pub fn malleate<T: SECP256>(&self, _: &T) -> Signature {
+        let n = &T::n();
+
+        Signature {
+            r: self.r.clone(),
+            s: n.sub_mod(&self.s, n),
+            recovery_id: self.recovery_id.map(RecoveryId::flip_y),
+        }
+    }
+
+    fn nonce_point<T: SECP256>(
+        &self,
+        recovery_id: RecoveryId,
+        curve: &T,
+    ) -> Result<ECAffinePoint, Error> {
+        let x = match recovery_id.x_reduced {
+            false => self.r.clone(),
+            true => RU256 {
+                v: self.r.v.checked_add(T::n().v).ok_or(Error::RNotOnCurve)?,
+            },
+        };
+
+        ECAffinePoint::from_x(&x, recovery_id.y_odd, curve).map_err(|_| Error::RNotOnCurve)
+    }
+}
+
+// Uniform scalar in [1, n - 1] from the OS CSPRNG, usable as a private key or nonce.
+// Candidates outside of the range are rejected and redrawn rather than reduced mod n,
+// which would bias the result towards small values.
+pub fn random_scalar<T: SECP256>(_: &T) -> Result<Zeroizing<RU256>, Error> {
+    let n = &T::n();
+    let mut bytes = Zeroizing::new([0u8; 32]);
+
+    loop {
+        getrandom::getrandom(bytes.as_mut()).map_err(|_| Error::RandomSource)?;
+        let candidate = Zeroizing::new(RU256::from_bytes(bytes.as_ref()));
+        if check_scalar(&candidate, n).is_ok() {
+            return Ok(candidate);
+        }
+    }
+}
+
+// c = (h / s) * G + (r / s) * PubKey has to be the nonce point R, so c.x % n == r
+fn check_verification_point<T: SECP256>(
+    c: &JacobianPoint,
+    r: &RU256,
+    curve: &T,
+) -> Result<(), Error> {
+    if c.is_zero_point() {
+        return Err(Error::InvalidSignature);
+    }
+
+    match (RU256 {
+        v: c.from_jacobian(curve).x.v % T::n().v,
+    }) == *r
+    {
+        true => Ok(()),
+        false => Err(Error::InvalidSignature),
+    }
+}
+
+// r and s have to be in [1, n - 1]
+fn check_scalar(scalar: &RU256, n: &RU256) -> Result<(), Error> {
+    if scalar == &RU256::zero() {
+        return Err(Error::ZeroScalar);
+    }
+    if scalar >= n {
+        return Err(Error::OutOfRange);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{random_scalar, recovery_id::RecoveryId, PrivateKey, Signature};
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::{affine::ECAffinePoint, ru256::RU256},
+        error::Error,
+    };
+    use primitive_types::U256;
+    use std::str::FromStr;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn test_recover_candidates() {
+        let priv_key = PrivateKey::new(
+            RU256::from_str("0xc1435991560e77992aaa190216c8939e3dc1855576a979963a3fd7110c04c316")
+                .unwrap(),
+        );
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let nonce = RU256::from_str("0x0a").unwrap();
+
+        let signature = priv_key.raw_sign(&msg_hash, &nonce, &K1);
+        let candidates = signature.raw_recover_candidates(&msg_hash, &K1);
+        assert!(candidates.contains(&(signature.recovery_id.unwrap(), priv_key.to_pub_key(&K1))));
+
+        let signature = priv_key.raw_sign(&msg_hash, &nonce, &R1);
+        let candidates = signature.raw_recover_candidates(&msg_hash, &R1);
+        assert!(candidates.contains(&(signature.recovery_id.unwrap(), priv_key.to_pub_key(&R1))));
+    }
+
+    #[test]
+    fn test_recover_overflowed_x() {
+        // a nonce point with n <= x < p, its discrete log is unknown so the
+        // signature is only valid for the key recovered from it
+        let n = K1::n();
+        let (r, encoded_nonce) = (1..)
+            .map(|j| RU256 { v: j.into() })
+            .find_map(|r| {
+                let x = r.add_mod(&n, &K1::p());
+                ECAffinePoint::from_x(&x, true, &K1)
+                    .ok()
+                    .map(|point| (r, point))
+            })
+            .unwrap();
+        assert!(encoded_nonce.x >= n);
+
+        let msg_hash = RU256::from_str("0x0123456789abcdef").unwrap();
+        let signature = Signature {
+            r,
+            s: RU256::from_str("0x3039").unwrap(),
+            recovery_id: Some(RecoveryId::new(true, true)),
+        };
+
+        let pub_key = signature.clone().raw_recover(&msg_hash, &K1).unwrap();
+        assert!(signature.raw_verify(&msg_hash, &pub_key, &K1).is_ok());
+        assert!(signature
+            .raw_recover_candidates(&msg_hash, &K1)
+            .contains(&(signature.recovery_id.unwrap(), pub_key)));
+    }
+
+    #[test]
+    fn test_recover_candidates_invalid() {
+        let signature = Signature {
+            r: RU256::zero(),
+            s: RU256::one(),
+            recovery_id: Some(RecoveryId::new(false, false)),
+        };
+
+        assert!(signature
+            .raw_recover_candidates(&RU256::one(), &K1)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recover_errors() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &K1);
+
+        let cases = [
+            (
+                Signature {
+                    recovery_id: None,
+                    ..signature.clone()
+                },
+                Error::InvalidV,
+            ),
+            (
+                Signature {
+                    r: K1::n(),
+                    ..signature.clone()
+                },
+                Error::OutOfRange,
+            ),
+            (
+                Signature {
+                    r: RU256 { v: K1::n().v + 1 },
+                    ..signature.clone()
+                },
+                Error::OutOfRange,
+            ),
+            (
+                Signature {
+                    s: K1::n(),
+                    ..signature.clone()
+                },
+                Error::OutOfRange,
+            ),
+            (
+                Signature {
+                    r: RU256::zero(),
+                    ..signature.clone()
+                },
+                Error::ZeroScalar,
+            ),
+            (
+                Signature {
+                    s: RU256::zero(),
+                    ..signature.clone()
+                },
+                Error::ZeroScalar,
+            ),
+            (
+                // 5 ** 3 + 7 is not a square mod p
+                Signature {
+                    r: RU256::from_str("0x05").unwrap(),
+                    ..signature.clone()
+                },
+                Error::RNotOnCurve,
+            ),
+        ];
+
+        for (invalid, expected) in cases {
+            assert_eq!(invalid.raw_recover(&msg_hash, &K1), Err(expected));
+        }
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let pub_key = priv_key.to_pub_key(&R1);
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &R1);
+
+        assert_eq!(
+            signature.raw_verify(&RU256::from_str("0x0a").unwrap(), &pub_key, &R1),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            Signature {
+                s: RU256::zero(),
+                ..signature.clone()
+            }
+            .raw_verify(&msg_hash, &pub_key, &R1),
+            Err(Error::ZeroScalar)
+        );
+        assert_eq!(
+            signature.raw_verify(&msg_hash, &ECAffinePoint::zero_point(), &R1),
+            Err(Error::PointAtInfinity)
+        );
+        assert_eq!(
+            signature.raw_verify(
+                &msg_hash,
+                &ECAffinePoint {
+                    x: pub_key.x.clone(),
+                    y: pub_key.x.clone(),
+                },
+                &R1
+            ),
+            Err(Error::PointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn test_verify_range_checks() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let pub_key = priv_key.to_pub_key(&K1);
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &K1);
+        let n = K1::n();
+
+        let cases = [
+            (
+                Signature {
+                    r: RU256::zero(),
+                    ..signature.clone()
+                },
+                Error::ZeroScalar,
+            ),
+            (
+                Signature {
+                    r: RU256 { v: n.v + 1 },
+                    ..signature.clone()
+                },
+                Error::OutOfRange,
+            ),
+            (
+                Signature {
+                    s: n.clone(),
+                    ..signature.clone()
+                },
+                Error::OutOfRange,
+            ),
+        ];
+
+        for (invalid, expected) in cases {
+            assert_eq!(invalid.raw_verify(&msg_hash, &pub_key, &K1), Err(expected));
+        }
+    }
+
+    #[test]
+    fn test_verify_strict() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let pub_key = priv_key.to_pub_key(&K1);
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), &K1);
+        let high_s = Signature {
+            s: K1::n().sub_mod(&signature.s, &K1::n()),
+            ..signature.clone()
+        };
+
+        assert!(signature
+            .raw_verify_strict(&msg_hash, &pub_key, &K1)
+            .is_ok());
+        assert!(high_s.raw_verify(&msg_hash, &pub_key, &K1).is_ok());
+        assert_eq!(
+            high_s.raw_verify_strict(&msg_hash, &pub_key, &K1),
+            Err(Error::HighS)
+        );
+    }
+
+    #[test]
+    fn test_private_key_range() {
+        fn check<T: SECP256>(curve: &T) {
+            let n = T::n();
+            let n_minus_1 = n.sub_mod(&RU256::one(), &T::p());
+
+            assert!(PrivateKey::try_new(RU256::one(), curve).is_ok());
+            assert!(PrivateKey::try_new(n_minus_1, curve).is_ok());
+            assert!(matches!(
+                PrivateKey::try_new(RU256::zero(), curve),
+                Err(Error::ZeroScalar)
+            ));
+            assert!(matches!(
+                PrivateKey::try_new(n, curve),
+                Err(Error::OutOfRange)
+            ));
+        }
+        check(&K1);
+        check(&R1);
+    }
+
+    #[test]
+    fn test_random_private_key() {
+        let msg_hash = RU256::from_str("0x09").unwrap();
+
+        let priv_key = PrivateKey::random(&K1).unwrap();
+        assert!(PrivateKey::try_new(priv_key.0.clone(), &K1).is_ok());
+        assert!(PrivateKey::random(&K1).unwrap().0 != priv_key.0);
+
+        let nonce = random_scalar(&R1).unwrap();
+        assert!(nonce.v != U256::zero() && nonce.v < R1::n().v);
+        let priv_key = PrivateKey::random(&R1).unwrap();
+        let signature = priv_key.raw_sign(&msg_hash, &nonce, &R1);
+        assert!(signature
+            .raw_verify(&msg_hash, &priv_key.to_pub_key(&R1), &R1)
+            .is_ok());
+
+        let mut secret = RU256::from_str("0x2a").unwrap();
+        secret.zeroize();
+        assert!(secret == RU256::zero());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        fn check<T: SECP256>(curve: &T) {
+            let keys = ["0x2a", "0x0539", "0xc0ffee"]
+                .map(|k| PrivateKey::new(RU256::from_str(k).unwrap()));
+            let pub_keys: Vec<ECAffinePoint> = keys.iter().map(|k| k.to_pub_key(curve)).collect();
+            let msg_hashes = ["0x09", "0x0a", "0x0b"].map(|h| RU256::from_str(h).unwrap());
+            let signatures: Vec<Signature> = keys
+                .iter()
+                .zip(&msg_hashes)
+                .zip(["0x0b", "0x0c", "0x0d"])
+                .map(|((key, msg_hash), nonce)| {
+                    key.raw_sign(msg_hash, &RU256::from_str(nonce).unwrap(), curve)
+                })
+                .collect();
+            let batch = |signatures: &[Signature]| -> Vec<usize> {
+                let batch: Vec<_> = msg_hashes
+                    .iter()
+                    .zip(&pub_keys)
+                    .zip(signatures)
+                    .map(|((msg_hash, pub_key), signature)| (msg_hash, pub_key, signature))
+                    .collect();
+                Signature::raw_verify_batch(&batch, curve).unwrap()
+            };
+
+            assert_eq!(batch(&signatures), Vec::<usize>::new());
+
+            // still valid ECDSA without a recovery id or with the wrong one
+            let mut fallback = signatures.clone();
+            fallback[0].recovery_id = None;
+            fallback[2].recovery_id = fallback[2].recovery_id.map(RecoveryId::flip_y);
+            assert_eq!(batch(&fallback), Vec::<usize>::new());
+
+            let mut invalid = signatures.clone();
+            invalid[1].s = invalid[1].s.add_mod(&RU256::one(), &T::n());
+            assert_eq!(batch(&invalid), vec![1]);
+            invalid[2].r = RU256::zero();
+            assert_eq!(batch(&invalid), vec![1, 2]);
+        }
+        check(&K1);
+        check(&R1);
+
+        assert_eq!(Signature::raw_verify_batch(&[], &K1), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_malleability() {
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let msg_hash = RU256::from_str("0x09").unwrap();
+        let nonce = RU256::from_str("0x0b").unwrap();
+
+        fn check<T: SECP256>(priv_key: &PrivateKey, msg_hash: &RU256, nonce: &RU256, curve: &T) {
+            let pub_key = priv_key.to_pub_key(curve);
+            let signature = priv_key.raw_sign(msg_hash, nonce, curve);
+            assert!(signature.is_low_s(curve));
+            assert_eq!(signature.normalize_s(curve), signature);
+
+            let malleated = signature.malleate(curve);
+            assert!(!malleated.is_low_s(curve));
+            assert_ne!(malleated.recovery_id, signature.recovery_id);
+            assert!(malleated.raw_verify(msg_hash, &pub_key, curve).is_ok());
+            assert_eq!(
+                malleated.clone().raw_recover(msg_hash, curve).unwrap(),
+                pub_key
+            );
+            assert_eq!(malleated.normalize_s(curve), signature);
+            assert_eq!(malleated.malleate(curve), signature);
+        }
+        check(&priv_key, &msg_hash, &nonce, &K1);
+        check(&priv_key, &msg_hash, &nonce, &R1);
+
+        // n / 2 is the largest low s, signatures without a recovery id keep none
+        let signature = Signature {
+            r: RU256::one(),
+            s: K1::n_div_2(),
+            recovery_id: None,
+        };
+        assert!(signature.is_low_s(&K1));
+        let malleated = signature.malleate(&K1);
+        assert!(!malleated.is_low_s(&K1));
+        assert_eq!(malleated.recovery_id, None);
+
+        let overflowed = Signature {
+            recovery_id: Some(RecoveryId::new(true, true)),
+            ..signature
+        };
+        assert_eq!(
+            overflowed.malleate(&K1).recovery_id,
+            Some(RecoveryId::new(false, true))
+        );
+    }
+}
@@ -0,0 +1,683 @@
+use super::{
+    check_scalar,
+    schnorr::{SchnorrSignature, XOnlyPublicKey},
+    PrivateKey,
+};
+use crate::{
+    curves::{k1::K1, SECP256},
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+    hash::tagged_hash,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+// ******************************************************************
+// MuSig2 n-of-n Schnorr Multi-Signatures over secp256k1
+// keys:   Q = Σ a_i * P_i, a_i = hash("KeyAgg coefficient", L || pk_i)
+// round 1: every signer publishes R_i1 = k_i1 * G, R_i2 = k_i2 * G
+// round 2: R = R_1 + b * R_2, s_i = k_i1 + b * k_i2 + e * a_i * d_i
+// the aggregate (R.x, Σ s_i) is a BIP-340 signature for xbytes(Q)
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+// ******************************************************************
+
+#[derive(Debug, Clone)]
+pub struct KeyAggContext {
+    pub_keys: Vec<[u8; 33]>,
+    second_key: Option<[u8; 33]>,
+    list_hash: [u8; 32],
+    q: ECAffinePoint,
+    // accumulated sign flips and tweaks of Q
+    gacc: RU256,
+    tacc: RU256,
+}
+
+impl KeyAggContext {
+    pub fn new(pub_keys: &[[u8; 33]]) -> Result<Self, Error> {
+        let points = pub_keys
+            .iter()
+            .map(|pub_key| Ok(ECAffinePoint::from_compressed(pub_key, &K1)?.to_jacobian()))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let chunks: Vec<&[u8]> = pub_keys.iter().map(|pub_key| pub_key.as_slice()).collect();
+        let mut ctx = Self {
+            pub_keys: pub_keys.to_vec(),
+            // the first key that differs from pk_1 gets a_i = 1
+            second_key: pub_keys
+                .iter()
+                .find(|pub_key| Some(*pub_key) != pub_keys.first())
+                .copied(),
+            list_hash: tagged_hash("KeyAgg list", &chunks),
+            q: ECAffinePoint::zero_point(),
+            gacc: RU256::one(),
+            tacc: RU256::zero(),
+        };
+
+        let coefficients: Vec<RU256> = pub_keys
+            .iter()
+            .map(|pub_key| ctx.coefficient(pub_key))
+            .collect();
+        let q = JacobianPoint::multi_scalar_multiplication(&points, &coefficients, &K1);
+        if q.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+
+        ctx.q = q.from_jacobian(&K1);
+        Ok(ctx)
+    }
+
+    // Plain (is_xonly = false, BIP-32 style) or x-only (BIP-341 style) tweak of Q
+    pub fn apply_tweak(&self, tweak: &[u8; 32], is_xonly: bool) -> Result<Self, Error> {
+        /*
+         * g = -1 if x-only and Q.y is odd else 1
+         * Q' = g * Q + t * G
+         * gacc' = g * gacc, tacc' = t + g * tacc
+         */
+        let n = &K1::n();
+        let g = match is_xonly && !has_even_y(&self.q) {
+            true => n.sub_mod(&RU256::one(), n),
+            false => RU256::one(),
+        };
+        let t = RU256::from_bytes(tweak);
+        if &t >= n {
+            return Err(Error::OutOfRange);
+        }
+
+        let q =
+            K1::g()
+                .to_jacobian()
+                .strauss_shamir_multiplication(&self.q.to_jacobian(), &t, &g, &K1);
+        if q.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+
+        Ok(Self {
+            q: q.from_jacobian(&K1),
+            gacc: g.mul_mod(&self.gacc, n),
+            tacc: t.add_mod(&g.mul_mod(&self.tacc, n), n),
+            ..self.clone()
+        })
+    }
+
+    pub fn aggregate_public_key(&self) -> XOnlyPublicKey {
+        XOnlyPublicKey::from_bytes(&xbytes(&self.q)).expect("Q is on the curve")
+    }
+
+    // the full point Q, for further plain tweaking outside of MuSig2
+    pub fn aggregate_point(&self) -> &ECAffinePoint {
+        &self.q
+    }
+
+    fn coefficient(&self, pub_key: &[u8; 33]) -> RU256 {
+        match Some(pub_key) == self.second_key.as_ref() {
+            true => RU256::one(),
+            false => hash_to_scalar("KeyAgg coefficient", &[&self.list_hash, pub_key]),
+        }
+    }
+
+    // coefficient of a signer, who has to be part of the key set
+    fn signer_coefficient(&self, pub_key: &[u8; 33]) -> Result<RU256, Error> {
+        match self.pub_keys.contains(pub_key) {
+            true => Ok(self.coefficient(pub_key)),
+            false => Err(Error::UnknownKey),
+        }
+    }
+}
+
+// k_1 || k_2 || pk, consumed by partial_sign so that it can not be used twice
+pub struct SecretNonce([u8; 97]);
+
+impl SecretNonce {
+    pub fn from_bytes(bytes: [u8; 97]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Drop for SecretNonce {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+// Round 1, rand has to be fresh randomness for every call. The optional inputs
+// only add defense in depth against bad randomness.
+pub fn nonce_gen(
+    rand: &[u8; 32],
+    secret_key: Option<&PrivateKey>,
+    pub_key: &[u8; 33],
+    agg_pub_key: Option<&[u8; 32]>,
+    msg: Option<&[u8]>,
+    extra_in: Option<&[u8]>,
+) -> Result<(SecretNonce, [u8; 66]), Error> {
+    /*
+     * rand = bytes(sk) xor hash("MuSig/aux", rand') if sk is given else rand'
+     * k_i = hash("MuSig/nonce", rand || len(pk) || pk || len(aggpk) || aggpk
+     *                           || msg_prefixed || len(extra_in) || extra_in || i) % n
+     */
+    let mut rand = Zeroizing::new(*rand);
+    if let Some(secret_key) = secret_key {
+        let mut sk = Zeroizing::new([0u8; 32]);
+        secret_key.0.to_bytes(sk.as_mut());
+        let aux = tagged_hash("MuSig/aux", &[rand.as_ref()]);
+        rand.iter_mut()
+            .zip(sk.iter().zip(aux))
+            .for_each(|(r, (s, a))| *r = s ^ a);
+    }
+
+    let agg_pub_key = agg_pub_key.map(|pk| pk.as_slice()).unwrap_or_default();
+    let msg_prefixed = match msg {
+        Some(msg) => [&[1], &(msg.len() as u64).to_be_bytes()[..], msg].concat(),
+        None => vec![0],
+    };
+    let extra_in = extra_in.unwrap_or_default();
+
+    let mut sec_nonce = SecretNonce([0; 97]);
+    let mut pub_nonce = [0; 66];
+    for i in 0..2 {
+        let k = Zeroizing::new(hash_to_scalar(
+            "MuSig/nonce",
+            &[
+                rand.as_ref(),
+                &[pub_key.len() as u8],
+                pub_key,
+                &[agg_pub_key.len() as u8],
+                agg_pub_key,
+                &msg_prefixed,
+                &(extra_in.len() as u32).to_be_bytes(),
+                extra_in,
+                &[i as u8],
+            ],
+        ));
+        check_scalar(&k, &K1::n())?;
+
+        k.to_bytes(&mut sec_nonce.0[i * 32..(i + 1) * 32]);
+        let r = K1::g().to_jacobian().multiply(&k, &K1).from_jacobian(&K1);
+        pub_nonce[i * 33..(i + 1) * 33].copy_from_slice(&r.to_compressed());
+    }
+    sec_nonce.0[64..].copy_from_slice(pub_key);
+
+    Ok((sec_nonce, pub_nonce))
+}
+
+// R_j = Σ R_ij, an infinite R_j is encoded as 33 zero bytes
+pub fn nonce_agg(pub_nonces: &[[u8; 66]]) -> Result<[u8; 66], Error> {
+    let mut agg_nonce = [0; 66];
+
+    for j in 0..2 {
+        let mut r = JacobianPoint::zero_point();
+        for pub_nonce in pub_nonces {
+            let r_ij = ECAffinePoint::from_compressed(
+                pub_nonce[j * 33..(j + 1) * 33].try_into().unwrap(),
+                &K1,
+            )?;
+            r = r.add(&r_ij.to_jacobian(), &K1);
+        }
+
+        if !r.is_zero_point() {
+            agg_nonce[j * 33..(j + 1) * 33].copy_from_slice(&r.from_jacobian(&K1).to_compressed());
+        }
+    }
+
+    Ok(agg_nonce)
+}
+
+// Everything the signers of one message derive from the keys, the aggregate
+// nonce and the message
+#[derive(Debug, Clone)]
+pub struct Session {
+    key_agg: KeyAggContext,
+    // nonce coefficient
+    b: RU256,
+    r: ECAffinePoint,
+    // challenge
+    e: RU256,
+}
+
+impl Session {
+    pub fn new(key_agg: &KeyAggContext, agg_nonce: &[u8; 66], msg: &[u8]) -> Result<Self, Error> {
+        /*
+         * b = hash("MuSig/noncecoef", aggnonce || xbytes(Q) || m) % n
+         * R = R_1 + b * R_2, or G if that is infinite
+         * e = hash("BIP0340/challenge", xbytes(R) || xbytes(Q) || m) % n
+         */
+        let q_x = xbytes(&key_agg.q);
+        let b = hash_to_scalar("MuSig/noncecoef", &[agg_nonce, &q_x, msg]);
+
+        let r_1 = point_ext(agg_nonce[..33].try_into().unwrap())?;
+        let r_2 = point_ext(agg_nonce[33..].try_into().unwrap())?;
+        let r = r_1.to_jacobian().strauss_shamir_multiplication(
+            &r_2.to_jacobian(),
+            &RU256::one(),
+            &b,
+            &K1,
+        );
+        let r = match r.is_zero_point() {
+            true => K1::g(),
+            false => r.from_jacobian(&K1),
+        };
+
+        let e = hash_to_scalar("BIP0340/challenge", &[&xbytes(&r), &q_x, msg]);
+        Ok(Self {
+            key_agg: key_agg.clone(),
+            b,
+            r,
+            e,
+        })
+    }
+
+    pub fn partial_sig_verify(
+        &self,
+        partial_sig: &RU256,
+        pub_nonce: &[u8; 66],
+        pub_key: &[u8; 33],
+    ) -> Result<(), Error> {
+        /*
+         * Re = R_i1 + b * R_i2, negated if R.y is odd
+         * g' = (-1 if Q.y is odd else 1) * gacc
+         * s_i * G == Re + (e * a_i * g') * P_i
+         */
+        let n = &K1::n();
+        if partial_sig >= n {
+            return Err(Error::OutOfRange);
+        }
+
+        let r_1 = ECAffinePoint::from_compressed(pub_nonce[..33].try_into().unwrap(), &K1)?;
+        let r_2 = ECAffinePoint::from_compressed(pub_nonce[33..].try_into().unwrap(), &K1)?;
+        let re = r_1
+            .to_jacobian()
+            .strauss_shamir_multiplication(&r_2.to_jacobian(), &RU256::one(), &self.b, &K1)
+            .from_jacobian(&K1);
+        let re = match has_even_y(&self.r) {
+            true => re,
+            false => re.negate(&K1),
+        };
+
+        let point = ECAffinePoint::from_compressed(pub_key, &K1)?;
+        let a = self.key_agg.signer_coefficient(pub_key)?;
+        let g = self.g().mul_mod(&self.key_agg.gacc, n);
+
+        // s_i * G - (e * a_i * g') * P_i
+        let c = K1::g()
+            .to_jacobian()
+            .strauss_shamir_multiplication(
+                &point.to_jacobian(),
+                partial_sig,
+                &n.sub_mod(&self.e.mul_mod(&a, n).mul_mod(&g, n), n),
+                &K1,
+            )
+            .from_jacobian(&K1);
+
+        match c == re {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+
+    pub fn partial_sig_agg(&self, partial_sigs: &[RU256]) -> Result<SchnorrSignature, Error> {
+        /*
+         * s = Σ s_i + e * g * tacc
+         */
+        let n = &K1::n();
+        let mut s = self.e.mul_mod(&self.g(), n).mul_mod(&self.key_agg.tacc, n);
+        for partial_sig in partial_sigs {
+            if partial_sig >= n {
+                return Err(Error::OutOfRange);
+            }
+            s = s.add_mod(partial_sig, n);
+        }
+
+        Ok(SchnorrSignature {
+            r: self.r.x.clone(),
+            s,
+        })
+    }
+
+    // -1 if Q.y is odd else 1
+    fn g(&self) -> RU256 {
+        let n = &K1::n();
+        match has_even_y(&self.key_agg.q) {
+            true => RU256::one(),
+            false => n.sub_mod(&RU256::one(), n),
+        }
+    }
+}
+
+impl PrivateKey {
+    pub fn musig2_partial_sign(
+        &self,
+        sec_nonce: SecretNonce,
+        session: &Session,
+    ) -> Result<RU256, Error> {
+        /*
+         * k_j = k_j' if R.y is even else n - k_j'
+         * d = g * gacc * d'
+         * s_i = k_1 + b * k_2 + e * a_i * d
+         */
+        let n = &K1::n();
+        let mut k =
+            [0, 1].map(|j| Zeroizing::new(RU256::from_bytes(&sec_nonce.0[j * 32..(j + 1) * 32])));
+        for k in &k {
+            check_scalar(k, n)?;
+        }
+        if !has_even_y(&session.r) {
+            k = k.map(|k| Zeroizing::new(n.sub_mod(&k, n)));
+        }
+
+        check_scalar(&self.0, n)?;
+        let pub_key = self.to_pub_key(&K1).to_compressed();
+        if sec_nonce.0[64..] != pub_key {
+            return Err(Error::UnknownKey);
+        }
+        let a = session.key_agg.signer_coefficient(&pub_key)?;
+
+        let d = Zeroizing::new(
+            session
+                .g()
+                .mul_mod(&session.key_agg.gacc, n)
+                .mul_mod(&self.0, n),
+        );
+
+        Ok(k[0]
+            .add_mod(&session.b.mul_mod(&k[1], n), n)
+            .add_mod(&session.e.mul_mod(&a, n).mul_mod(&d, n), n))
+    }
+}
+
+fn hash_to_scalar(tag: &str, data: &[&[u8]]) -> RU256 {
+    RU256 {
+        v: RU256::from_bytes(&tagged_hash(tag, data)).v % K1::n().v,
+    }
+}
+
+fn has_even_y(point: &ECAffinePoint) -> bool {
+    !point.y.v.bit(0)
+}
+
+fn xbytes(point: &ECAffinePoint) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    point.x.to_bytes(&mut bytes);
+    bytes
+}
+
+// like from_compressed, with 33 zero bytes as the point at infinity
+fn point_ext(bytes: &[u8; 33]) -> Result<ECAffinePoint, Error> {
+    match bytes == &[0; 33] {
+        true => Ok(ECAffinePoint::zero_point()),
+        false => ECAffinePoint::from_compressed(bytes, &K1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{nonce_agg, nonce_gen, KeyAggContext, SecretNonce, Session};
+    use crate::{
+        curves::{k1::K1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::PrivateKey,
+    };
+
+    // https://github.com/bitcoin/bips/tree/master/bip-0327/vectors
+    fn decode<const N: usize>(hex: &str) -> [u8; N] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_key_agg_vectors() {
+        let pub_keys: Vec<[u8; 33]> = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+            "020000000000000000000000000000000000000000000000000000000000000005",
+            "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            "04F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        ]
+        .map(decode)
+        .to_vec();
+        let keys = |indices: &[usize]| indices.iter().map(|&i| pub_keys[i]).collect::<Vec<_>>();
+
+        let valid = [
+            (
+                vec![0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                vec![2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                vec![0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                vec![0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+        for (indices, expected) in valid {
+            let ctx = KeyAggContext::new(&keys(&indices)).unwrap();
+            assert_eq!(
+                ctx.aggregate_public_key().to_bytes(),
+                decode::<32>(expected)
+            );
+        }
+
+        // x is not on the curve / exceeds the field size / invalid prefix
+        assert_eq!(
+            KeyAggContext::new(&keys(&[0, 3])).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        assert_eq!(
+            KeyAggContext::new(&keys(&[0, 4])).unwrap_err(),
+            Error::OutOfRange
+        );
+        assert_eq!(
+            KeyAggContext::new(&keys(&[5, 0])).unwrap_err(),
+            Error::InvalidEncoding
+        );
+
+        // tweak is out of range / the tweaked key is infinite
+        let ctx = KeyAggContext::new(&keys(&[0, 1])).unwrap();
+        assert_eq!(
+            ctx.apply_tweak(&[0xff; 32], true).unwrap_err(),
+            Error::OutOfRange
+        );
+        let ctx = KeyAggContext::new(&keys(&[6])).unwrap();
+        let tweak = decode("252E4BD67410A76CDF933D30EAA1608214037F1B105A013ECCD3C5C184A6110B");
+        assert_eq!(
+            ctx.apply_tweak(&tweak, false).unwrap_err(),
+            Error::PointAtInfinity
+        );
+    }
+
+    #[test]
+    fn test_nonce_gen_vector() {
+        let (sec_nonce, pub_nonce) = nonce_gen(
+            &[0x0f; 32],
+            Some(&PrivateKey::new(RU256::from_bytes(&[0x02; 32]))),
+            &decode("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766"),
+            Some(&[0x07; 32]),
+            Some(&[0x01; 32]),
+            Some(&[0x08; 32]),
+        )
+        .unwrap();
+        assert_eq!(
+            sec_nonce.0,
+            decode::<97>("B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB6495B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766")
+        );
+        assert_eq!(nonce_agg(&[pub_nonce]).unwrap(), pub_nonce);
+    }
+
+    #[test]
+    fn test_nonce_agg_vectors() {
+        let pub_nonces: Vec<[u8; 66]> = [
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E66603BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E6660279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60379BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        ]
+        .map(decode)
+        .to_vec();
+
+        assert_eq!(
+            nonce_agg(&pub_nonces[..2]).unwrap(),
+            decode::<66>("035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8")
+        );
+        // R_2 sums to infinity
+        assert_eq!(
+            nonce_agg(&pub_nonces[2..]).unwrap(),
+            decode::<66>("035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B000000000000000000000000000000000000000000000000000000000000000000")
+        );
+
+        let mut invalid = pub_nonces[1];
+        invalid[33] = 0x04;
+        assert_eq!(
+            nonce_agg(&[pub_nonces[0], invalid]),
+            Err(Error::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_sign_verify_vectors() {
+        let priv_key = PrivateKey::new(RU256::from_bytes(&decode::<32>(
+            "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671",
+        )));
+        let pub_keys: Vec<[u8; 33]> = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+        ]
+        .map(decode)
+        .to_vec();
+        let sec_nonce = || {
+            let mut bytes = [0; 97];
+            bytes[..64].copy_from_slice(&hex::decode("508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7").unwrap());
+            bytes[64..].copy_from_slice(&pub_keys[0]);
+            SecretNonce::from_bytes(bytes)
+        };
+        let pub_nonces: Vec<[u8; 66]> = [
+            "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+            "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        ]
+        .map(decode)
+        .to_vec();
+        let msg = hex::decode("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF")
+            .unwrap();
+
+        assert_eq!(priv_key.to_pub_key(&K1).to_compressed(), pub_keys[0]);
+        assert_eq!(
+            nonce_agg(&pub_nonces[..3]).unwrap(),
+            decode::<66>("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9")
+        );
+        assert_eq!(nonce_agg(&[pub_nonces[0], pub_nonces[3]]).unwrap(), [0; 66]);
+
+        let valid = [
+            (
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                vec![1, 0, 2],
+                vec![1, 0, 2],
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                vec![1, 2, 0],
+                vec![1, 2, 0],
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+            // both halves of the aggregate nonce are infinite
+            (
+                vec![0, 1],
+                vec![0, 3],
+                "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531",
+            ),
+        ];
+        for (key_indices, nonce_indices, expected) in valid {
+            let keys: Vec<[u8; 33]> = key_indices.iter().map(|&i| pub_keys[i]).collect();
+            let nonces: Vec<[u8; 66]> = nonce_indices.iter().map(|&i| pub_nonces[i]).collect();
+            let ctx = KeyAggContext::new(&keys).unwrap();
+            let session = Session::new(&ctx, &nonce_agg(&nonces).unwrap(), &msg).unwrap();
+
+            let partial_sig = priv_key.musig2_partial_sign(sec_nonce(), &session).unwrap();
+            assert_eq!(partial_sig, RU256::from_bytes(&decode::<32>(expected)));
+
+            let signer = key_indices.iter().position(|&i| i == 0).unwrap();
+            assert!(session
+                .partial_sig_verify(&partial_sig, &nonces[signer], &pub_keys[0])
+                .is_ok());
+            assert_eq!(
+                session.partial_sig_verify(
+                    &partial_sig,
+                    &nonces[(signer + 1) % nonces.len()],
+                    &pub_keys[0]
+                ),
+                Err(Error::InvalidSignature)
+            );
+        }
+
+        // the signer is not part of the key set
+        let ctx = KeyAggContext::new(&pub_keys[1..]).unwrap();
+        let session = Session::new(&ctx, &nonce_agg(&pub_nonces[..3]).unwrap(), &msg).unwrap();
+        assert_eq!(
+            priv_key.musig2_partial_sign(sec_nonce(), &session),
+            Err(Error::UnknownKey)
+        );
+    }
+
+    #[test]
+    fn test_sign_tweaked() {
+        let priv_keys = [3, 5].map(|key| PrivateKey::new(RU256::from_bytes(&[key; 32])));
+        let pub_keys: Vec<[u8; 33]> = priv_keys
+            .iter()
+            .map(|priv_key| priv_key.to_pub_key(&K1).to_compressed())
+            .collect();
+        let ctx = KeyAggContext::new(&pub_keys)
+            .unwrap()
+            .apply_tweak(&[0x11; 32], false)
+            .unwrap()
+            .apply_tweak(&[0x22; 32], true)
+            .unwrap();
+        let msg = b"MuSig2 round trip";
+
+        let (sec_nonces, pub_nonces): (Vec<_>, Vec<_>) = priv_keys
+            .iter()
+            .zip(&pub_keys)
+            .enumerate()
+            .map(|(i, (priv_key, pub_key))| {
+                let x_only = ctx.aggregate_public_key().to_bytes();
+                nonce_gen(
+                    &[i as u8; 32],
+                    Some(priv_key),
+                    pub_key,
+                    Some(&x_only),
+                    Some(msg),
+                    None,
+                )
+                .unwrap()
+            })
+            .unzip();
+        let session = Session::new(&ctx, &nonce_agg(&pub_nonces).unwrap(), msg).unwrap();
+
+        let partial_sigs: Vec<RU256> = priv_keys
+            .iter()
+            .zip(sec_nonces)
+            .map(|(priv_key, sec_nonce)| priv_key.musig2_partial_sign(sec_nonce, &session).unwrap())
+            .collect();
+        for i in 0..2 {
+            assert!(session
+                .partial_sig_verify(&partial_sigs[i], &pub_nonces[i], &pub_keys[i])
+                .is_ok());
+        }
+
+        let signature = session.partial_sig_agg(&partial_sigs).unwrap();
+        assert!(signature.verify(msg, &ctx.aggregate_public_key()).is_ok());
+
+        let signature = session.partial_sig_agg(&partial_sigs[..1]).unwrap();
+        assert!(signature.verify(msg, &ctx.aggregate_public_key()).is_err());
+        assert_eq!(session.partial_sig_agg(&[K1::n()]), Err(Error::OutOfRange));
+    }
+}
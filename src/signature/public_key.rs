@@ -0,0 +1,243 @@
+use super::{check_scalar, check_verification_point, Signature};
+use crate::{
+    curves::SECP256,
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+};
+
+// ******************************************************************
+// Public Key
+// a point that is on the curve and not the identity, with the window
+// tables of the point and of G cached for repeated verification
+// SEC1 encoding: 0x02 / 0x03 || x (compressed, by parity of y)
+//                0x04 || x || y (uncompressed)
+// Reference: https://www.secg.org/sec1-v2.pdf (2.3.3, 2.3.4)
+// ******************************************************************
+
+#[derive(Debug, Clone)]
+pub struct PublicKey<C: SECP256> {
+    point: ECAffinePoint,
+    jacobian: JacobianPoint,
+    table: Vec<JacobianPoint>,
+    g_table: Vec<JacobianPoint>,
+    curve: C,
+}
+
+impl<C: SECP256 + Clone> PublicKey<C> {
+    pub fn from_point(point: ECAffinePoint, curve: &C) -> Result<Self, Error> {
+        point.validate(curve)?;
+
+        let jacobian = point.to_jacobian();
+        Ok(Self {
+            table: jacobian.window_table(curve),
+            g_table: C::g().to_jacobian().window_table(curve),
+            point,
+            jacobian,
+            curve: curve.clone(),
+        })
+    }
+
+    pub fn from_sec1(bytes: &[u8], curve: &C) -> Result<Self, Error> {
+        let point = match (bytes.first(), bytes.len()) {
+            (Some(0x02 | 0x03), 33) => {
+                ECAffinePoint::from_compressed(bytes.try_into().unwrap(), curve)?
+            }
+            (Some(0x04), 65) => ECAffinePoint {
+                x: RU256::from_bytes(&bytes[1..33]),
+                y: RU256::from_bytes(&bytes[33..]),
+            },
+            _ => return Err(Error::InvalidEncoding),
+        };
+
+        Self::from_point(point, curve)
+    }
+
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        match compressed {
+            true => self.point.to_compressed().to_vec(),
+            false => {
+                let mut bytes = vec![0; 65];
+                bytes[0] = 0x04;
+                self.point.x.to_bytes(&mut bytes[1..33]);
+                self.point.y.to_bytes(&mut bytes[33..]);
+                bytes
+            }
+        }
+    }
+
+    pub fn point(&self) -> &ECAffinePoint {
+        &self.point
+    }
+
+    pub fn jacobian(&self) -> &JacobianPoint {
+        &self.jacobian
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    // Same checks as Signature::raw_verify, using the cached window tables
+    pub fn verify(&self, msg_hash: &RU256, signature: &Signature) -> Result<(), Error> {
+        let n = &C::n();
+        check_scalar(&signature.r, n)?;
+        check_scalar(&signature.s, n)?;
+
+        let s_inv = RU256::one().div_mod(&signature.s, n);
+        let a = msg_hash.mul_mod(&s_inv, n);
+        let b = signature.r.mul_mod(&s_inv, n);
+        let c = JacobianPoint::windowed_strauss_shamir_multiplication(
+            &self.g_table,
+            &self.table,
+            &a,
+            &b,
+            &self.curve,
+        );
+
+        check_verification_point(&c, &signature.r, &self.curve)
+    }
+
+    pub fn verify_strict(&self, msg_hash: &RU256, signature: &Signature) -> Result<(), Error> {
+        if !signature.is_low_s(&self.curve) {
+            return Err(Error::HighS);
+        }
+
+        self.verify(msg_hash, signature)
+    }
+}
+
+impl<C: SECP256> PartialEq for PublicKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PublicKey;
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::{affine::ECAffinePoint, ru256::RU256},
+        error::Error,
+        signature::{PrivateKey, Signature},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_from_point() {
+        assert_eq!(
+            PublicKey::from_point(ECAffinePoint::zero_point(), &K1),
+            Err(Error::PointAtInfinity)
+        );
+        assert_eq!(
+            PublicKey::from_point(
+                ECAffinePoint {
+                    x: RU256::one(),
+                    y: RU256::one(),
+                },
+                &R1
+            ),
+            Err(Error::PointNotOnCurve)
+        );
+
+        let pub_key = PublicKey::from_point(K1::g(), &K1).unwrap();
+        assert_eq!(pub_key.point(), &K1::g());
+        assert_eq!(pub_key.jacobian().from_jacobian(&K1), K1::g());
+    }
+
+    #[test]
+    fn test_sec1() {
+        // G of secp256k1, SEC 2 2.4.1
+        let pub_key = PublicKey::from_point(K1::g(), &K1).unwrap();
+        let compressed =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let uncompressed = hex::decode("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8").unwrap();
+
+        assert_eq!(pub_key.to_sec1(true), compressed);
+        assert_eq!(pub_key.to_sec1(false), uncompressed);
+        assert_eq!(PublicKey::from_sec1(&compressed, &K1).unwrap(), pub_key);
+        assert_eq!(PublicKey::from_sec1(&uncompressed, &K1).unwrap(), pub_key);
+
+        let priv_key = PrivateKey::new(RU256::from_str("0x2a").unwrap());
+        let pub_key = priv_key.public_key(&R1).unwrap();
+        for compressed in [true, false] {
+            let encoded = pub_key.to_sec1(compressed);
+            assert_eq!(PublicKey::from_sec1(&encoded, &R1).unwrap(), pub_key);
+        }
+
+        let invalid = [
+            "",
+            "00",
+            // hybrid encoding is not supported
+            "0679be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            // wrong length for the prefix
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179800",
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ];
+        for encoded in invalid {
+            assert_eq!(
+                PublicKey::from_sec1(&hex::decode(encoded).unwrap(), &K1),
+                Err(Error::InvalidEncoding)
+            );
+        }
+
+        // y is not the square root of x ** 3 + 7
+        let mut off_curve = hex::decode(invalid[2]).unwrap();
+        off_curve[0] = 0x04;
+        off_curve[64] ^= 1;
+        assert_eq!(
+            PublicKey::from_sec1(&off_curve, &K1),
+            Err(Error::PointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        fn check<T: SECP256 + Clone>(curve: &T) {
+            let priv_key = PrivateKey::new(
+                RU256::from_str(
+                    "0xc1435991560e77992aaa190216c8939e3dc1855576a979963a3fd7110c04c316",
+                )
+                .unwrap(),
+            );
+            let pub_key = priv_key.public_key(curve).unwrap();
+            let msg_hash = RU256::from_str("0x09").unwrap();
+            let signature = priv_key.raw_sign(&msg_hash, &RU256::from_str("0x0b").unwrap(), curve);
+
+            assert!(pub_key.verify(&msg_hash, &signature).is_ok());
+            assert!(pub_key.verify_strict(&msg_hash, &signature).is_ok());
+
+            let high_s = signature.malleate(curve);
+            assert!(pub_key.verify(&msg_hash, &high_s).is_ok());
+            assert_eq!(pub_key.verify_strict(&msg_hash, &high_s), Err(Error::HighS));
+
+            assert_eq!(
+                pub_key.verify(&RU256::from_str("0x0a").unwrap(), &signature),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(
+                pub_key.verify(
+                    &msg_hash,
+                    &Signature {
+                        s: T::n(),
+                        ..signature.clone()
+                    }
+                ),
+                Err(Error::OutOfRange)
+            );
+            assert_eq!(
+                pub_key.verify(
+                    &msg_hash,
+                    &Signature {
+                        r: RU256::zero(),
+                        ..signature
+                    }
+                ),
+                Err(Error::ZeroScalar)
+            );
+        }
+        check(&K1);
+        check(&R1);
+    }
+}
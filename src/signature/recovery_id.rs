@@ -0,0 +1,121 @@
+use crate::{ecmaths::ru256::RU256, error::Error};
+use primitive_types::U256;
+
+// ******************************************************************
+// ECDSA Recovery Id
+// identifies the nonce point R among the candidates for a given r
+// raw:      y_odd + 2 * x_reduced               (0..=3)
+// legacy:   27 + raw                            (27..=30, ecrecover, Bitcoin message signing)
+// EIP-155:  35 + 2 * chain_id + y_odd           (x_reduced can not be represented)
+// Reference: https://eips.ethereum.org/EIPS/eip-155
+// ******************************************************************
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryId {
+    // R.y is odd
+    pub y_odd: bool,
+    // R.x >= n, so r = R.x - n
+    pub x_reduced: bool,
+}
+
+impl RecoveryId {
+    pub fn new(y_odd: bool, x_reduced: bool) -> Self {
+        Self { y_odd, x_reduced }
+    }
+
+    pub fn to_byte(self) -> u8 {
+        self.y_odd as u8 | (self.x_reduced as u8) << 1
+    }
+
+    pub fn from_byte(byte: u8) -> Result<Self, Error> {
+        match byte {
+            0..=3 => Ok(Self::new(byte & 1 == 1, byte & 2 == 2)),
+            _ => Err(Error::InvalidV),
+        }
+    }
+
+    pub fn to_legacy(self) -> u8 {
+        self.to_byte() + 27
+    }
+
+    pub fn from_legacy(v: u8) -> Result<Self, Error> {
+        match v {
+            27..=30 => Self::from_byte(v - 27),
+            _ => Err(Error::InvalidV),
+        }
+    }
+
+    pub fn to_eip155(self, chain_id: u64) -> Result<RU256, Error> {
+        if self.x_reduced {
+            return Err(Error::InvalidV);
+        }
+
+        Ok(RU256 {
+            v: U256::from(chain_id) * 2 + 35 + self.y_odd as u64,
+        })
+    }
+
+    pub fn from_eip155(v: &RU256, chain_id: u64) -> Result<Self, Error> {
+        let offset = U256::from(chain_id) * 2 + 35;
+        if v.v < offset || v.v - offset > U256::one() {
+            return Err(Error::InvalidV);
+        }
+
+        Ok(Self::new(v.v - offset == U256::one(), false))
+    }
+
+    // the recovery id of -R, the nonce point of (r, n - s)
+    pub fn flip_y(self) -> Self {
+        Self::new(!self.y_odd, self.x_reduced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecoveryId;
+    use crate::{ecmaths::ru256::RU256, error::Error};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_conversions() {
+        for byte in 0..4 {
+            let recovery_id = RecoveryId::from_byte(byte).unwrap();
+            assert_eq!(recovery_id.to_byte(), byte);
+            assert_eq!(recovery_id.to_legacy(), byte + 27);
+            assert_eq!(RecoveryId::from_legacy(byte + 27), Ok(recovery_id));
+            assert_eq!(recovery_id.flip_y().flip_y(), recovery_id);
+            assert_ne!(recovery_id.flip_y(), recovery_id);
+        }
+        assert_eq!(RecoveryId::from_byte(4), Err(Error::InvalidV));
+        assert_eq!(RecoveryId::from_legacy(26), Err(Error::InvalidV));
+        assert_eq!(RecoveryId::from_legacy(31), Err(Error::InvalidV));
+    }
+
+    #[test]
+    fn test_eip155() {
+        let even = RecoveryId::new(false, false);
+        let odd = RecoveryId::new(true, false);
+
+        // mainnet v = 37 / 38
+        assert_eq!(even.to_eip155(1), Ok(RU256::from_str("0x25").unwrap()));
+        assert_eq!(odd.to_eip155(1), Ok(RU256::from_str("0x26").unwrap()));
+        assert_eq!(
+            RecoveryId::from_eip155(&RU256::from_str("0x26").unwrap(), 1),
+            Ok(odd)
+        );
+
+        let v = odd.to_eip155(u64::MAX).unwrap();
+        assert_eq!(RecoveryId::from_eip155(&v, u64::MAX), Ok(odd));
+
+        assert_eq!(
+            RecoveryId::new(false, true).to_eip155(1),
+            Err(Error::InvalidV)
+        );
+        for v in ["0x24", "0x27", "0x1b"] {
+            assert_eq!(
+                RecoveryId::from_eip155(&RU256::from_str(v).unwrap(), 1),
+                Err(Error::InvalidV)
+            );
+        }
+    }
+}
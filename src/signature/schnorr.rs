@@ -0,0 +1,339 @@
+use super::{check_scalar, random_scalar, PrivateKey};
+use crate::{
+    curves::{k1::K1, SECP256},
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+    hash::tagged_hash,
+};
+use zeroize::Zeroizing;
+
+// ******************************************************************
+// BIP-340 Schnorr Signatures over secp256k1
+// public keys are x-only, the point with that x and an even y
+// signature: bytes(R.x) || bytes(s) with s * G = R + e * P
+// e = tagged_hash("BIP0340/challenge", R.x || P.x || m) % n
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+// ******************************************************************
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct XOnlyPublicKey(ECAffinePoint);
+
+impl XOnlyPublicKey {
+    // lift_x, fails if x >= p or x is not on the curve
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        Ok(Self(ECAffinePoint::from_x(
+            &RU256::from_bytes(bytes),
+            false,
+            &K1,
+        )?))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        self.0.x.to_bytes(&mut bytes);
+        bytes
+    }
+
+    // the even y point
+    pub fn point(&self) -> &ECAffinePoint {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchnorrSignature {
+    // x coordinate of the nonce point R
+    pub r: RU256,
+    pub s: RU256,
+}
+
+impl SchnorrSignature {
+    // r < p and s < n are checked in verify
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        Self {
+            r: RU256::from_bytes(&bytes[..32]),
+            s: RU256::from_bytes(&bytes[32..]),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        self.r.to_bytes(&mut bytes[..32]);
+        self.s.to_bytes(&mut bytes[32..]);
+        bytes
+    }
+
+    pub fn verify(&self, msg: &[u8], pub_key: &XOnlyPublicKey) -> Result<(), Error> {
+        /*
+         * e = hash(r || P.x || m) % n
+         * R = s * G - e * P
+         * R is not infinity, R.y is even and R.x == r
+         */
+        let n = &K1::n();
+        if self.r >= K1::p() || &self.s >= n {
+            return Err(Error::OutOfRange);
+        }
+
+        let e = challenge(&self.r, pub_key, msg);
+        let c = K1::g().to_jacobian().strauss_shamir_multiplication(
+            &pub_key.0.to_jacobian(),
+            &self.s,
+            &n.sub_mod(&e, n),
+            &K1,
+        );
+        if c.is_zero_point() {
+            return Err(Error::InvalidSignature);
+        }
+
+        let nonce_point = c.from_jacobian(&K1);
+        match !nonce_point.y.v.bit(0) && nonce_point.x == self.r {
+            true => Ok(()),
+            false => Err(Error::InvalidSignature),
+        }
+    }
+
+    // Returns the indices of the signatures that fail, empty if the whole batch is valid
+    pub fn verify_batch(
+        batch: &[(&[u8], &XOnlyPublicKey, &SchnorrSignature)],
+    ) -> Result<Vec<usize>, Error> {
+        /*
+         * a_0 = 1, a_i random in [1, n - 1]
+         * R_i = lift_x(r_i)
+         * (Σ a_i * s_i) * G = Σ a_i * R_i + Σ (a_i * e_i) * P_i
+         *
+         * checked as a single multi scalar multiplication
+         * -(Σ a_i * s_i) * G + Σ a_i * R_i + Σ (a_i * e_i) * P_i == 0
+         */
+        let n = &K1::n();
+        let mut points = vec![K1::g().to_jacobian()];
+        let mut scalars = vec![RU256::zero()];
+        let mut g_scalar = RU256::zero();
+
+        for (i, (msg, pub_key, signature)) in batch.iter().enumerate() {
+            if signature.r >= K1::p() || &signature.s >= n {
+                return Ok(Self::failures(batch));
+            }
+            let nonce_point = match ECAffinePoint::from_x(&signature.r, false, &K1) {
+                Ok(nonce_point) => nonce_point,
+                Err(_) => return Ok(Self::failures(batch)),
+            };
+
+            let a = match i {
+                0 => RU256::one(),
+                _ => (*random_scalar(&K1)?).clone(),
+            };
+            let e = challenge(&signature.r, pub_key, msg);
+
+            g_scalar = g_scalar.add_mod(&a.mul_mod(&signature.s, n), n);
+            points.push(nonce_point.to_jacobian());
+            scalars.push(a.clone());
+            points.push(pub_key.0.to_jacobian());
+            scalars.push(a.mul_mod(&e, n));
+        }
+        scalars[0] = n.sub_mod(&g_scalar, n);
+
+        match JacobianPoint::multi_scalar_multiplication(&points, &scalars, &K1).is_zero_point() {
+            true => Ok(vec![]),
+            false => Ok(Self::failures(batch)),
+        }
+    }
+
+    // the batch equation only says that some signature is invalid, find which one(s)
+    fn failures(batch: &[(&[u8], &XOnlyPublicKey, &SchnorrSignature)]) -> Vec<usize> {
+        batch
+            .iter()
+            .enumerate()
+            .filter(|(_, (msg, pub_key, signature))| signature.verify(msg, pub_key).is_err())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl PrivateKey {
+    pub fn x_only_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        check_scalar(&self.0, &K1::n())?;
+        let point = self.to_pub_key(&K1);
+        Ok(XOnlyPublicKey(ECAffinePoint {
+            y: match point.y.v.bit(0) {
+                true => K1::p().sub_mod(&point.y, &K1::p()),
+                false => point.y,
+            },
+            x: point.x,
+        }))
+    }
+
+    pub fn sign_schnorr(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Result<SchnorrSignature, Error> {
+        /*
+         * P = d' * G, d = d' if P.y is even else n - d'
+         * t = bytes(d) xor hash("BIP0340/aux", a)
+         * k' = hash("BIP0340/nonce", t || P.x || m) % n
+         * R = k' * G, k = k' if R.y is even else n - k'
+         * s = (k + e * d) % n
+         */
+        let n = &K1::n();
+        check_scalar(&self.0, n)?;
+
+        let point = self.to_pub_key(&K1);
+        let d = Zeroizing::new(match point.y.v.bit(0) {
+            true => n.sub_mod(&self.0, n),
+            false => self.0.clone(),
+        });
+        let pub_key = self.x_only_public_key()?;
+
+        let mut t = Zeroizing::new([0u8; 32]);
+        d.to_bytes(t.as_mut());
+        let aux_hash = tagged_hash("BIP0340/aux", &[aux_rand]);
+        t.iter_mut().zip(aux_hash).for_each(|(t, a)| *t ^= a);
+
+        let rand = Zeroizing::new(tagged_hash(
+            "BIP0340/nonce",
+            &[t.as_ref(), &pub_key.to_bytes(), msg],
+        ));
+        let k = Zeroizing::new(RU256 {
+            v: RU256::from_bytes(rand.as_ref()).v % n.v,
+        });
+        check_scalar(&k, n)?;
+
+        let nonce_point = K1::g().to_jacobian().multiply(&k, &K1).from_jacobian(&K1);
+        let k = Zeroizing::new(match nonce_point.y.v.bit(0) {
+            true => n.sub_mod(&k, n),
+            false => (*k).clone(),
+        });
+
+        let e = challenge(&nonce_point.x, &pub_key, msg);
+        let signature = SchnorrSignature {
+            r: nonce_point.x,
+            s: k.add_mod(&e.mul_mod(&d, n), n),
+        };
+
+        // guards against faults in the arithmetic leaking the key
+        signature.verify(msg, &pub_key)?;
+        Ok(signature)
+    }
+}
+
+fn challenge(r: &RU256, pub_key: &XOnlyPublicKey, msg: &[u8]) -> RU256 {
+    let mut r_bytes = [0; 32];
+    r.to_bytes(&mut r_bytes);
+
+    let e = tagged_hash("BIP0340/challenge", &[&r_bytes, &pub_key.to_bytes(), msg]);
+    RU256 {
+        v: RU256::from_bytes(&e).v % K1::n().v,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SchnorrSignature, XOnlyPublicKey};
+    use crate::{
+        curves::{k1::K1, SECP256},
+        ecmaths::ru256::RU256,
+        signature::PrivateKey,
+    };
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    const TEST_VECTORS: &str = "index,secret key,public key,aux_rand,message,signature,verification result,comment
+0,0000000000000000000000000000000000000000000000000000000000000003,F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9,0000000000000000000000000000000000000000000000000000000000000000,0000000000000000000000000000000000000000000000000000000000000000,E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0,TRUE,
+1,B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,0000000000000000000000000000000000000000000000000000000000000001,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A,TRUE,
+2,C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9,DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8,C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906,7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C,5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7,TRUE,
+3,0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710,25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF,7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3,TRUE,test fails if msg is reduced modulo p or n
+4,,D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9,,4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703,00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4,TRUE,
+5,,EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key not on the curve
+6,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2,FALSE,has_even_y(R) is false
+7,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD,FALSE,negated message
+8,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6,FALSE,negated s value
+9,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 0
+10,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197,FALSE,sG - eP is infinite. Test fails in single verification if has_even_y(inf) is defined as true and x(inf) as 1
+11,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is not an X coordinate on the curve
+12,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,sig[0:32] is equal to field size
+13,,DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141,FALSE,sig[32:64] is equal to curve order
+14,,FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30,,243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89,6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B,FALSE,public key is not a valid X coordinate because it exceeds the field size
+15,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,,71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63,TRUE,message of size 0 (added 2022-12)
+16,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,11,08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF,TRUE,message of size 1 (added 2022-12)
+17,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,0102030405060708090A0B0C0D0E0F1011,5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5,TRUE,message of size 17 (added 2022-12)
+18,0340034003400340034003400340034003400340034003400340034003400340,778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117,0000000000000000000000000000000000000000000000000000000000000000,99999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999,403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367,TRUE,message of size 100 (added 2022-12)";
+
+    #[test]
+    fn test_bip340_vectors() {
+        for line in TEST_VECTORS.lines().skip(1) {
+            let fields: Vec<&str> = line.split(',').collect();
+            let (index, secret_key, pub_key, aux_rand, msg, signature, result) = (
+                fields[0], fields[1], fields[2], fields[3], fields[4], fields[5], fields[6],
+            );
+            let msg = hex::decode(msg).unwrap();
+            let signature: [u8; 64] = hex::decode(signature).unwrap().try_into().unwrap();
+
+            if !secret_key.is_empty() {
+                let priv_key =
+                    PrivateKey::new(RU256::from_bytes(&hex::decode(secret_key).unwrap()));
+                let aux_rand: [u8; 32] = hex::decode(aux_rand).unwrap().try_into().unwrap();
+
+                assert_eq!(
+                    hex::encode_upper(priv_key.x_only_public_key().unwrap().to_bytes()),
+                    pub_key,
+                    "vector {}",
+                    index
+                );
+                assert_eq!(
+                    priv_key.sign_schnorr(&msg, &aux_rand).unwrap().to_bytes(),
+                    signature,
+                    "vector {}",
+                    index
+                );
+            }
+
+            let pub_key: [u8; 32] = hex::decode(pub_key).unwrap().try_into().unwrap();
+            let verified = XOnlyPublicKey::from_bytes(&pub_key).and_then(|pub_key| {
+                SchnorrSignature::from_bytes(&signature).verify(&msg, &pub_key)
+            });
+            assert_eq!(verified.is_ok(), result == "TRUE", "vector {}", index);
+        }
+    }
+
+    fn vector(index: usize) -> (Vec<u8>, XOnlyPublicKey, SchnorrSignature) {
+        let fields: Vec<&str> = TEST_VECTORS
+            .lines()
+            .nth(index + 1)
+            .unwrap()
+            .split(',')
+            .collect();
+        let pub_key: [u8; 32] = hex::decode(fields[2]).unwrap().try_into().unwrap();
+        let signature: [u8; 64] = hex::decode(fields[5]).unwrap().try_into().unwrap();
+
+        (
+            hex::decode(fields[4]).unwrap(),
+            XOnlyPublicKey::from_bytes(&pub_key).unwrap(),
+            SchnorrSignature::from_bytes(&signature),
+        )
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        // valid: 0, 1, 2, 4, 17; invalid: 7 (negated message), 11 (r not on curve),
+        // 12 (r = p)
+        let vectors: Vec<_> = [0, 1, 7, 2, 4, 11, 17, 12]
+            .into_iter()
+            .map(vector)
+            .collect();
+        let batch: Vec<_> = vectors
+            .iter()
+            .map(|(msg, pub_key, signature)| (msg.as_slice(), pub_key, signature))
+            .collect();
+
+        let valid = [0, 1, 3, 4, 6].map(|i| batch[i]);
+        assert_eq!(SchnorrSignature::verify_batch(&valid), Ok(vec![]));
+        assert_eq!(SchnorrSignature::verify_batch(&[]), Ok(vec![]));
+
+        assert_eq!(SchnorrSignature::verify_batch(&batch[..5]), Ok(vec![2]));
+        assert_eq!(SchnorrSignature::verify_batch(&batch), Ok(vec![2, 5, 7]));
+    }
+
+    #[test]
+    fn test_sign_invalid_key() {
+        for key in [RU256::zero(), K1::n()] {
+            let priv_key = PrivateKey::new(key);
+            assert!(priv_key.x_only_public_key().is_err());
+            assert!(priv_key.sign_schnorr(&[], &[0; 32]).is_err());
+        }
+    }
+}
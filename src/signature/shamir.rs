@@ -0,0 +1,380 @@
+use super::{check_scalar, random_scalar, PrivateKey};
+use crate::{
+    curves::SECP256,
+    ecmaths::{affine::ECAffinePoint, jacobian::JacobianPoint, ru256::RU256},
+    error::Error,
+};
+use primitive_types::U256;
+use zeroize::Zeroize;
+
+// ******************************************************************
+// Shamir Secret Sharing over Z_n with Feldman VSS
+// split:   f(x) = s + a_1 * x + ... + a_(t-1) * x^(t-1), share i is (i, f(i))
+// combine: s = f(0) = Σ λ_i * f(i), λ_i = Π (0 - x_j) / (x_i - x_j) for j != i
+// Feldman: C_j = a_j * G, share i is valid if f(i) * G == Σ i^j * C_j
+// Reference: https://dl.acm.org/doi/10.1145/359168.359176
+//            https://www.cs.umd.edu/~gasarch/TOPICS/secretsharing/feldmanVSS.pdf
+// ******************************************************************
+
+pub struct Share {
+    pub identifier: RU256,
+    pub value: PrivateKey,
+}
+
+// coefficients of f, f(0) first
+pub struct Polynomial(Vec<RU256>);
+
+impl Polynomial {
+    // A random polynomial of degree threshold - 1 with f(0) = secret
+    pub fn random<T: SECP256>(
+        secret: &PrivateKey,
+        threshold: u16,
+        curve: &T,
+    ) -> Result<Self, Error> {
+        let mut coefficients = vec![secret.0.clone()];
+        for _ in 1..threshold {
+            coefficients.push((*random_scalar(curve)?).clone());
+        }
+
+        Self::from_coefficients(coefficients, curve)
+    }
+
+    pub fn from_coefficients<T: SECP256>(coefficients: Vec<RU256>, _: &T) -> Result<Self, Error> {
+        // only the secret f(0) has to be non-zero
+        let polynomial = Self(coefficients);
+        let n = &T::n();
+        check_scalar(polynomial.0.first().ok_or(Error::OutOfRange)?, n)?;
+        if polynomial.0.iter().any(|coefficient| coefficient >= n) {
+            return Err(Error::OutOfRange);
+        }
+
+        Ok(polynomial)
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.0.len() as u16
+    }
+
+    pub fn evaluate<T: SECP256>(&self, x: &RU256, _: &T) -> RU256 {
+        // Horner: f(x) = c_0 + x * (c_1 + x * (...))
+        let n = &T::n();
+        self.0
+            .iter()
+            .rev()
+            .fold(RU256::zero(), |acc, c| acc.mul_mod(x, n).add_mod(c, n))
+    }
+
+    // shares for the identifiers 1..=count
+    pub fn shares<T: SECP256>(&self, count: u16, curve: &T) -> Result<Vec<Share>, Error> {
+        if count < self.threshold() {
+            return Err(Error::OutOfRange);
+        }
+
+        Ok((1..=count)
+            .map(|i| {
+                let identifier = RU256 { v: U256::from(i) };
+                Share {
+                    value: PrivateKey(self.evaluate(&identifier, curve)),
+                    identifier,
+                }
+            })
+            .collect())
+    }
+
+    // Feldman commitment C_j = a_j * G, C_0 is the public key of the secret
+    pub fn commitment<T: SECP256>(&self, curve: &T) -> Vec<ECAffinePoint> {
+        let g = T::g().to_jacobian();
+        self.0
+            .iter()
+            .map(|c| g.multiply(c, curve).from_jacobian(curve))
+            .collect()
+    }
+}
+
+impl Drop for Polynomial {
+    fn drop(&mut self) {
+        self.0.iter_mut().for_each(|c| c.zeroize());
+    }
+}
+
+// Splits secret into count shares of which any threshold recover it
+pub fn split<T: SECP256>(
+    secret: &PrivateKey,
+    threshold: u16,
+    count: u16,
+    curve: &T,
+) -> Result<Vec<Share>, Error> {
+    Polynomial::random(secret, threshold, curve)?.shares(count, curve)
+}
+
+// Recovers f(0) from at least threshold shares
+pub fn combine<T: SECP256>(shares: &[Share], curve: &T) -> Result<PrivateKey, Error> {
+    let n = &T::n();
+    let identifiers: Vec<RU256> = shares
+        .iter()
+        .map(|share| share.identifier.clone())
+        .collect();
+
+    let mut secret = RU256::zero();
+    for share in shares {
+        let lambda = lagrange_coefficient(&identifiers, &share.identifier, &RU256::zero(), curve)?;
+        secret = secret.add_mod(&lambda.mul_mod(&share.value.0, n), n);
+    }
+
+    check_scalar(&secret, n)?;
+    Ok(PrivateKey(secret))
+}
+
+// Lagrange basis polynomial of identifier over identifiers, evaluated at x
+pub fn lagrange_coefficient<T: SECP256>(
+    identifiers: &[RU256],
+    identifier: &RU256,
+    x: &RU256,
+    _: &T,
+) -> Result<RU256, Error> {
+    /*
+     * λ_i(x) = Π (x - x_j) / (x_i - x_j) for j != i
+     */
+    let n = &T::n();
+    for (i, x_j) in identifiers.iter().enumerate() {
+        check_scalar(x_j, n)?;
+        if identifiers[..i].contains(x_j) {
+            return Err(Error::InvalidEncoding);
+        }
+    }
+    if !identifiers.contains(identifier) {
+        return Err(Error::UnknownKey);
+    }
+
+    let (numerator, denominator) = identifiers.iter().filter(|x_j| x_j != &identifier).fold(
+        (RU256::one(), RU256::one()),
+        |(num, den), x_j| {
+            (
+                num.mul_mod(&x.sub_mod(x_j, n), n),
+                den.mul_mod(&identifier.sub_mod(x_j, n), n),
+            )
+        },
+    );
+
+    Ok(numerator.div_mod(&denominator, n))
+}
+
+// Feldman check of a share against the dealer's commitment
+pub fn verify_share<T: SECP256>(
+    share: &Share,
+    commitment: &[ECAffinePoint],
+    curve: &T,
+) -> Result<(), Error> {
+    /*
+     * f(i) * G == Σ i^j * C_j
+     */
+    let n = &T::n();
+    check_scalar(&share.identifier, n)?;
+    check_scalar(&share.value.0, n)?;
+    // C_0 commits to the secret, a zero higher order coefficient commits to
+    // the identity, which adds nothing to the sum
+    commitment
+        .first()
+        .ok_or(Error::OutOfRange)?
+        .validate(curve)?;
+    for c in &commitment[1..] {
+        if !c.is_zero_point() {
+            c.validate(curve)?;
+        }
+    }
+
+    let mut points = vec![T::g().to_jacobian()];
+    let mut scalars = vec![n.sub_mod(&share.value.0, n)];
+    let mut power = RU256::one();
+    for c in commitment {
+        if !c.is_zero_point() {
+            points.push(c.to_jacobian());
+            scalars.push(power.clone());
+        }
+        power = power.mul_mod(&share.identifier, n);
+    }
+
+    match JacobianPoint::multi_scalar_multiplication(&points, &scalars, curve).is_zero_point() {
+        true => Ok(()),
+        false => Err(Error::InvalidSignature),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{combine, lagrange_coefficient, split, verify_share, Polynomial, Share};
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::PrivateKey,
+    };
+    use std::str::FromStr;
+
+    fn scalar(value: &str) -> RU256 {
+        RU256::from_str(value).unwrap()
+    }
+
+    // f(x) = s + a_1 * x + a_2 * x^2 with fixed coefficients, and f(5) for the curve
+    const SECRET: &str = "0x8f2a55949038a9610f50fb23b5883af3b4ecb3c3bb792cbcefbd1542c692be63";
+    const COEFFICIENTS: [&str; 2] = [
+        "0xe1f0ccba6e1e16c0b0f48a0a2b02f9d1f18e6b9fd0a3d1c18b0e2f7e0b5c3a91",
+        "0x5b2e7e49e1d6f60f3b4a7c9e0d2c1b8a7f6e5d4c3b2a19080706050403020100",
+    ];
+
+    #[test]
+    fn test_split_combine() {
+        fn check<T: SECP256>(curve: &T, share_5: &str) {
+            let secret = PrivateKey::new(scalar(SECRET));
+            let polynomial = Polynomial::from_coefficients(
+                [SECRET]
+                    .iter()
+                    .chain(&COEFFICIENTS)
+                    .map(|c| scalar(c))
+                    .collect(),
+                curve,
+            )
+            .unwrap();
+            let shares = polynomial.shares(5, curve).unwrap();
+            assert_eq!(shares.len(), 5);
+            assert_eq!(shares[4].identifier, scalar("0x5"));
+            assert_eq!(shares[4].value.0, scalar(share_5));
+
+            let commitment = polynomial.commitment(curve);
+            for share in &shares {
+                assert!(verify_share(share, &commitment, curve).is_ok());
+            }
+
+            for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+                let subset: Vec<Share> = subset
+                    .iter()
+                    .map(|&i| Share {
+                        identifier: shares[i].identifier.clone(),
+                        value: PrivateKey(shares[i].value.0.clone()),
+                    })
+                    .collect();
+                assert_eq!(combine(&subset, curve).unwrap().0, secret.0);
+            }
+            // below the threshold the result is unrelated to the secret
+            assert_ne!(combine(&shares[..2], curve).unwrap().0, secret.0);
+            assert_eq!(combine(&shares, curve).unwrap().0, secret.0);
+
+            assert_eq!(polynomial.shares(2, curve).err(), Some(Error::OutOfRange));
+            assert_eq!(split(&secret, 3, 2, curve).err(), Some(Error::OutOfRange));
+        }
+        check(
+            &K1,
+            "0xe068aa6fc4cd22a14e5dd8c5d5e5cca4639ab29daf18944498ebb2f6b7d0abeb",
+        );
+        check(
+            &R1,
+            "0xe068aa7cc4cd22944e5dd8c5d5e5cc9746c12f831995aa8cf62b34367989171b",
+        );
+    }
+
+    #[test]
+    fn test_lagrange_coefficient() {
+        let identifiers = [scalar("0x1"), scalar("0x2"), scalar("0x3")];
+        let n = &K1::n();
+
+        // λ_1(0) = 3, λ_2(0) = -3, λ_3(0) = 1
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &identifiers[0], &RU256::zero(), &K1),
+            Ok(scalar("0x3"))
+        );
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &identifiers[1], &RU256::zero(), &K1),
+            Ok(n.sub_mod(&scalar("0x3"), n))
+        );
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &identifiers[2], &RU256::zero(), &K1),
+            Ok(RU256::one())
+        );
+        // λ_i(x_j) = 1 if i == j else 0
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &identifiers[1], &identifiers[1], &K1),
+            Ok(RU256::one())
+        );
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &identifiers[1], &identifiers[2], &K1),
+            Ok(RU256::zero())
+        );
+
+        assert_eq!(
+            lagrange_coefficient(&identifiers, &scalar("0x4"), &RU256::zero(), &K1),
+            Err(Error::UnknownKey)
+        );
+        assert_eq!(
+            lagrange_coefficient(
+                &[scalar("0x1"), scalar("0x1")],
+                &scalar("0x1"),
+                &RU256::zero(),
+                &K1
+            ),
+            Err(Error::InvalidEncoding)
+        );
+        assert_eq!(
+            lagrange_coefficient(&[RU256::zero()], &RU256::zero(), &RU256::one(), &K1),
+            Err(Error::ZeroScalar)
+        );
+    }
+
+    #[test]
+    fn test_feldman() {
+        // f(x) = 7 + 5x + 3x^2
+        let polynomial =
+            Polynomial::from_coefficients(vec![scalar("0x7"), scalar("0x5"), scalar("0x3")], &R1)
+                .unwrap();
+        assert_eq!(polynomial.evaluate(&scalar("0x2"), &R1), scalar("0x1d"));
+
+        let commitment = polynomial.commitment(&R1);
+        assert_eq!(
+            commitment[0],
+            PrivateKey::new(scalar("0x7")).to_pub_key(&R1)
+        );
+
+        let shares = polynomial.shares(4, &R1).unwrap();
+        for share in &shares {
+            assert!(verify_share(share, &commitment, &R1).is_ok());
+        }
+
+        let forged = Share {
+            identifier: shares[0].identifier.clone(),
+            value: PrivateKey(shares[1].value.0.clone()),
+        };
+        assert_eq!(
+            verify_share(&forged, &commitment, &R1),
+            Err(Error::InvalidSignature)
+        );
+        // commitment to a different polynomial
+        let other = Polynomial::from_coefficients(vec![scalar("0x7"), scalar("0x6")], &R1).unwrap();
+        assert_eq!(
+            verify_share(&shares[2], &other.commitment(&R1), &R1),
+            Err(Error::InvalidSignature)
+        );
+
+        assert_eq!(
+            Polynomial::from_coefficients(vec![], &R1).err(),
+            Some(Error::OutOfRange)
+        );
+
+        // zero higher order coefficients lower the degree, f(x) = 7 + 3x^2
+        let sparse =
+            Polynomial::from_coefficients(vec![scalar("0x7"), RU256::zero(), scalar("0x3")], &R1)
+                .unwrap();
+        assert_eq!(sparse.evaluate(&scalar("0x2"), &R1), scalar("0x13"));
+        let commitment = sparse.commitment(&R1);
+        for share in &sparse.shares(3, &R1).unwrap() {
+            assert!(verify_share(share, &commitment, &R1).is_ok());
+        }
+
+        assert_eq!(
+            Polynomial::from_coefficients(vec![RU256::zero(), scalar("0x5")], &R1).err(),
+            Some(Error::ZeroScalar)
+        );
+        assert_eq!(
+            Polynomial::from_coefficients(vec![scalar("0x7"), R1::n()], &R1).err(),
+            Some(Error::OutOfRange)
+        );
+    }
+}
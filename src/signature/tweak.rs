@@ -0,0 +1,271 @@
+use super::{check_scalar, public_key::PublicKey, PrivateKey};
+use crate::{curves::SECP256, ecmaths::ru256::RU256, error::Error};
+
+// ******************************************************************
+// Key Tweaking
+// add: d' = d + t mod n,  P' = P + t * G   (0 <= t < n)
+// mul: d' = d * t mod n,  P' = t * P       (0 < t < n)
+// negate: d' = n - d,     P' = -P
+// as in libsecp256k1 a tweak out of range, a zero private key or a point
+// at infinity is an error, so d' * G == P' holds for every result
+// Reference: https://github.com/bitcoin-core/secp256k1/blob/master/include/secp256k1.h
+//            (secp256k1_ec_seckey_tweak_add, secp256k1_ec_pubkey_tweak_mul, ...)
+// ******************************************************************
+
+impl PrivateKey {
+    pub fn tweak_add<T: SECP256>(&self, tweak: &RU256, _: &T) -> Result<Self, Error> {
+        let n = &T::n();
+        check_scalar(&self.0, n)?;
+        check_tweak(tweak, n)?;
+
+        let key = Self(self.0.add_mod(tweak, n));
+        check_scalar(&key.0, n)?;
+        Ok(key)
+    }
+
+    pub fn tweak_mul<T: SECP256>(&self, tweak: &RU256, _: &T) -> Result<Self, Error> {
+        let n = &T::n();
+        check_scalar(&self.0, n)?;
+        check_scalar(tweak, n)?;
+
+        Ok(Self(self.0.mul_mod(tweak, n)))
+    }
+
+    pub fn negate<T: SECP256>(&self, _: &T) -> Result<Self, Error> {
+        let n = &T::n();
+        check_scalar(&self.0, n)?;
+
+        Ok(Self(n.sub_mod(&self.0, n)))
+    }
+}
+
+impl<C: SECP256 + Clone> PublicKey<C> {
+    pub fn tweak_add(&self, tweak: &RU256) -> Result<Self, Error> {
+        check_tweak(tweak, &C::n())?;
+
+        let point = C::g().to_jacobian().strauss_shamir_multiplication(
+            self.jacobian(),
+            tweak,
+            &RU256::one(),
+            self.curve(),
+        );
+        if point.is_zero_point() {
+            return Err(Error::PointAtInfinity);
+        }
+
+        Self::from_point(point.from_jacobian(self.curve()), self.curve())
+    }
+
+    pub fn tweak_mul(&self, tweak: &RU256) -> Result<Self, Error> {
+        check_scalar(tweak, &C::n())?;
+
+        let point = self.jacobian().multiply(tweak, self.curve());
+        Self::from_point(point.from_jacobian(self.curve()), self.curve())
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::from_point(self.point().negate(self.curve()), self.curve())
+            .expect("the negation of a valid point is valid")
+    }
+}
+
+// an additive tweak may be zero
+fn check_tweak(tweak: &RU256, n: &RU256) -> Result<(), Error> {
+    match tweak < n {
+        true => Ok(()),
+        false => Err(Error::OutOfRange),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::{k1::K1, r1::R1, SECP256},
+        ecmaths::ru256::RU256,
+        error::Error,
+        signature::{public_key::PublicKey, PrivateKey},
+    };
+    use std::fmt::Debug;
+
+    fn scalar(hex: &str) -> RU256 {
+        RU256::from_bytes(&hex::decode(hex).unwrap())
+    }
+
+    // (private key, tweak): the BIP-341 key path spending input 0 for
+    // secp256k1, the NIST CAVS ECDH dIUT of COUNT = 0 and 1 for P-256
+    const K1_KEY: (&str, &str) = (
+        "6b973d88838f27366ed61c9ad6367663045cb456e28335c109e30717ae0c6baa",
+        "b86e7be8f39bab32a6f2c0443abbc210f0edac0e2c53d501b36b64437d9c6c70",
+    );
+    const R1_KEY: (&str, &str) = (
+        "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+        "38f65d6dce47676044d58ce5139582d568f64bb16098d179dbab07741dd5caf5",
+    );
+
+    #[test]
+    fn test_tweak_add() {
+        fn check<T: SECP256 + Clone + Debug>(curve: &T, (key, tweak): (&str, &str)) {
+            let n = &T::n();
+            let priv_key = PrivateKey::new(scalar(key));
+            let pub_key = priv_key.public_key(curve).unwrap();
+            let tweak = scalar(tweak);
+
+            let tweaked = priv_key.tweak_add(&tweak, curve).unwrap();
+            assert_eq!(tweaked.0, priv_key.0.add_mod(&tweak, n));
+            assert_eq!(
+                pub_key.tweak_add(&tweak).unwrap(),
+                tweaked.public_key(curve).unwrap()
+            );
+
+            // a zero tweak is allowed
+            assert_eq!(
+                priv_key.tweak_add(&RU256::zero(), curve).unwrap().0,
+                priv_key.0
+            );
+            assert_eq!(pub_key.tweak_add(&RU256::zero()).unwrap(), pub_key);
+
+            // t = n - d cancels the key
+            let cancel = n.sub_mod(&priv_key.0, n);
+            assert_eq!(
+                priv_key.tweak_add(&cancel, curve).err(),
+                Some(Error::ZeroScalar)
+            );
+            assert_eq!(
+                pub_key.tweak_add(&cancel).err(),
+                Some(Error::PointAtInfinity)
+            );
+
+            assert_eq!(priv_key.tweak_add(n, curve).err(), Some(Error::OutOfRange));
+            assert_eq!(pub_key.tweak_add(n).err(), Some(Error::OutOfRange));
+            assert_eq!(
+                PrivateKey::new(RU256::zero())
+                    .tweak_add(&tweak, curve)
+                    .err(),
+                Some(Error::ZeroScalar)
+            );
+        }
+        check(&K1, K1_KEY);
+        check(&R1, R1_KEY);
+
+        // BIP-341 taproot output key Q = P + t * G, the internal key has an
+        // even y so neither side is negated
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        let (key, tweak) = K1_KEY;
+        let priv_key = PrivateKey::new(scalar(key));
+        let pub_key = priv_key.public_key(&K1).unwrap();
+        assert_eq!(
+            hex::encode(pub_key.to_sec1(true)),
+            "02d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"
+        );
+        let tweaked = priv_key.tweak_add(&scalar(tweak), &K1).unwrap();
+        let mut tweaked_bytes = [0u8; 32];
+        tweaked.0.to_bytes(&mut tweaked_bytes);
+        assert_eq!(
+            hex::encode(tweaked_bytes),
+            "2405b971772ad26915c8dcdf10f238753a9b837e5f8e6a86fd7c0cce5b7296d9"
+        );
+        assert_eq!(
+            hex::encode(&pub_key.tweak_add(&scalar(tweak)).unwrap().to_sec1(true)[1..]),
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+        );
+    }
+
+    #[test]
+    fn test_tweak_mul() {
+        fn check<T: SECP256 + Clone + Debug>(curve: &T, (key, tweak): (&str, &str)) {
+            let n = &T::n();
+            let priv_key = PrivateKey::new(scalar(key));
+            let pub_key = priv_key.public_key(curve).unwrap();
+            let tweak = scalar(tweak);
+
+            let tweaked = priv_key.tweak_mul(&tweak, curve).unwrap();
+            assert_eq!(tweaked.0, priv_key.0.mul_mod(&tweak, n));
+            assert_eq!(
+                pub_key.tweak_mul(&tweak).unwrap(),
+                tweaked.public_key(curve).unwrap()
+            );
+            assert_eq!(pub_key.tweak_mul(&RU256::one()).unwrap(), pub_key);
+
+            assert_eq!(
+                priv_key.tweak_mul(&RU256::zero(), curve).err(),
+                Some(Error::ZeroScalar)
+            );
+            assert_eq!(
+                pub_key.tweak_mul(&RU256::zero()).err(),
+                Some(Error::ZeroScalar)
+            );
+            assert_eq!(priv_key.tweak_mul(n, curve).err(), Some(Error::OutOfRange));
+            assert_eq!(pub_key.tweak_mul(n).err(), Some(Error::OutOfRange));
+        }
+        check(&K1, K1_KEY);
+        check(&R1, R1_KEY);
+
+        // d * Q is the ECDH point, its x coordinate the shared secret of
+        // go-ethereum TestSharedKeyStatic and NIST CAVS ECDH P-256 COUNT = 0
+        let peer = PrivateKey::new(scalar(
+            "6a3d6396903245bba5837752b9e0348874e72db0c4e11e9c485a81b4ea4353b9",
+        ))
+        .public_key(&K1)
+        .unwrap();
+        let shared = peer
+            .tweak_mul(&scalar(
+                "7ebbc6a8358bc76dd73ebc557056702c8cfc34e5cfcd90eb83af0347575fd2ad",
+            ))
+            .unwrap();
+        assert_eq!(
+            hex::encode(&shared.to_sec1(true)[1..]),
+            "167ccc13ac5e8a26b131c3446030c60fbfac6aa8e31149d0869f93626a4cdf62"
+        );
+
+        let peer = PublicKey::from_sec1(
+            &hex::decode("04700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287db71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac").unwrap(),
+            &R1,
+        )
+        .unwrap();
+        let shared = peer.tweak_mul(&scalar(R1_KEY.0)).unwrap();
+        assert_eq!(
+            hex::encode(&shared.to_sec1(true)[1..]),
+            "46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b"
+        );
+    }
+
+    #[test]
+    fn test_negate() {
+        fn check<T: SECP256 + Clone + Debug>(curve: &T, (key, _): (&str, &str)) {
+            let priv_key = PrivateKey::new(scalar(key));
+            let pub_key = priv_key.public_key(curve).unwrap();
+
+            let negated = priv_key.negate(curve).unwrap();
+            assert_eq!(negated.0, T::n().sub_mod(&priv_key.0, &T::n()));
+            assert_eq!(negated.public_key(curve).unwrap(), pub_key.negate());
+            assert_eq!(negated.negate(curve).unwrap().0, priv_key.0);
+            assert_eq!(pub_key.negate().negate(), pub_key);
+            assert_eq!(pub_key.negate().point().x, pub_key.point().x);
+            // only the parity byte of the compressed encoding flips
+            let (sec1, negated_sec1) = (pub_key.to_sec1(true), pub_key.negate().to_sec1(true));
+            assert_eq!(sec1[0] ^ negated_sec1[0], 1);
+            assert_eq!(sec1[1..], negated_sec1[1..]);
+
+            // d + (-d) = 0
+            assert_eq!(
+                priv_key.tweak_add(&negated.0, curve).err(),
+                Some(Error::ZeroScalar)
+            );
+            assert_eq!(
+                PrivateKey::new(T::n()).negate(curve).err(),
+                Some(Error::OutOfRange)
+            );
+        }
+        check(&K1, K1_KEY);
+        check(&R1, R1_KEY);
+
+        // -1 * G on secp256k1
+        let g = PublicKey::from_point(K1::g(), &K1).unwrap();
+        let minus_one = PrivateKey::new(RU256::one()).negate(&K1).unwrap();
+        assert_eq!(minus_one.0, K1::n().sub_mod(&RU256::one(), &K1::n()));
+        assert_eq!(
+            hex::encode(g.negate().to_sec1(true)),
+            "0379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+    }
+}